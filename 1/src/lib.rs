@@ -0,0 +1,125 @@
+use std::fmt;
+use std::iter;
+
+pub mod report;
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Mode {
+    // fuel for the module mass only
+    Simple,
+    // fuel for the module, then fuel for that fuel, and so on
+    Recursive
+}
+
+pub fn compute_fuel(mass: u64) -> u64 {
+    (mass / 3).saturating_sub(2)
+}
+
+pub fn compute_recursive_fuel(mass: u64) -> u64 {
+    let fuel = compute_fuel(mass);
+    if fuel == 0 {
+        0
+    } else {
+        fuel + compute_recursive_fuel(fuel)
+    }
+}
+
+// Same as compute_recursive_fuel, without growing the stack.
+pub fn compute_iterative_fuel(mass: u64) -> u64 {
+    let mut total = 0;
+    let mut fuel = compute_fuel(mass);
+    while fuel > 0 {
+        total += fuel;
+        fuel = compute_fuel(fuel);
+    }
+    total
+}
+
+// Fuel for the mass, then fuel for that fuel, until no more fuel is needed.
+pub fn fuel_chain(mass: u64) -> impl Iterator<Item = u64> {
+    let positive = |fuel: u64| Some(fuel).filter(|fuel| *fuel > 0);
+    iter::successors(positive(compute_fuel(mass)), move |fuel| positive(compute_fuel(*fuel)))
+}
+
+pub fn module_fuel(mass: u64, mode: Mode) -> u64 {
+    match mode {
+        Mode::Simple => compute_fuel(mass),
+        Mode::Recursive => compute_iterative_fuel(mass)
+    }
+}
+
+// Summed as u128 so that any number of u64 masses fits.
+pub fn total_fuel<I: IntoIterator<Item = u64>>(masses: I, mode: Mode) -> u128 {
+    masses.into_iter().map(|mass| u128::from(module_fuel(mass, mode))).sum()
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct ParseError {
+    pub line: usize,
+    pub text: String,
+    pub reason: String
+}
+
+impl fmt::Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "line {}: invalid mass {:?} ({})", self.line, self.text, self.reason)
+    }
+}
+
+// Masses with their line numbers, starting at 1. Blank lines are skipped, and
+// every malformed line is reported.
+pub fn parse_masses(content: &str) -> Result<Vec<(usize, u64)>, Vec<ParseError>> {
+    let mut masses = Vec::<(usize, u64)>::new();
+    let mut errors = Vec::<ParseError>::new();
+    for (index, text) in content.lines().enumerate() {
+        if text.trim().is_empty() {
+            continue;
+        }
+        match text.trim().parse::<u64>() {
+            Ok(mass) => masses.push((index + 1, mass)),
+            Err(err) => errors.push(ParseError { line: index + 1, text: text.to_string(), reason: err.to_string() })
+        }
+    }
+    if errors.is_empty() { Ok(masses) } else { Err(errors) }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_simple_fuel() {
+        assert_eq!(compute_fuel(12), 2);
+        assert_eq!(compute_fuel(14), 2);
+        assert_eq!(compute_fuel(1969), 654);
+        assert_eq!(compute_fuel(100756), 33583);
+        assert_eq!(compute_fuel(5), 0);
+        assert_eq!(compute_fuel(u64::MAX), u64::MAX / 3 - 2);
+    }
+
+    #[test]
+    fn test_recursive_fuel() {
+        for (mass, fuel) in [(14, 2), (1969, 966), (100756, 50346)].iter() {
+            assert_eq!(compute_recursive_fuel(*mass), *fuel);
+            assert_eq!(compute_iterative_fuel(*mass), *fuel);
+        }
+        assert_eq!(compute_iterative_fuel(u64::MAX), compute_recursive_fuel(u64::MAX));
+        assert_eq!(fuel_chain(1969).collect::<Vec<u64>>(), vec![654, 216, 70, 21, 5]);
+        assert_eq!(fuel_chain(8).count(), 0);
+    }
+
+    #[test]
+    fn test_parse_masses() {
+        assert_eq!(parse_masses("12\n\n 14 \n"), Ok(vec![(1, 12), (3, 14)]));
+        let errors = parse_masses("12\n1.5\n-3\n14").unwrap_err();
+        assert_eq!(errors.iter().map(|err| err.line).collect::<Vec<usize>>(), vec![2, 3]);
+        assert_eq!(errors[0].to_string(), "line 2: invalid mass \"1.5\" (invalid digit found in string)");
+    }
+
+    #[test]
+    fn test_total_fuel() {
+        assert_eq!(total_fuel(vec![12, 14, 1969, 100756], Mode::Simple), 2 + 2 + 654 + 33583);
+        assert_eq!(total_fuel(vec![14, 1969, 100756], Mode::Recursive), 2 + 966 + 50346);
+        assert_eq!(total_fuel(vec![u64::MAX; 4], Mode::Simple), 4 * u128::from(u64::MAX / 3 - 2));
+    }
+}
@@ -0,0 +1,65 @@
+use std::env;
+use std::fs;
+use std::process;
+
+use fuel::report::FuelReport;
+use fuel::{parse_masses, total_fuel, Mode};
+
+#[derive(Clone, Copy, PartialEq)]
+enum Format {
+    Totals,
+    Table,
+    Csv,
+    Json
+}
+
+fn read_masses(filename: &str) -> Vec<(usize, u64)> {
+    let content = fs::read_to_string(filename).unwrap_or_else(|_| panic!("Error reading {}", filename));
+    parse_masses(&content).unwrap_or_else(|errors| {
+        for err in errors {
+            eprintln!("{}: {}", filename, err);
+        }
+        process::exit(1);
+    })
+}
+
+fn main() {
+    let args:Vec<String> = env::args().collect();
+    let mut input_filename = "input.txt".to_string();
+    let mut modes = vec![Mode::Simple, Mode::Recursive];
+    let mut format = Format::Totals;
+    let mut it = args.iter().skip(1);
+    while let Some(arg) = it.next() {
+        match arg.as_str() {
+            "--simple" => modes = vec![Mode::Simple],
+            "--recursive" => modes = vec![Mode::Recursive],
+            "--report" => format = Format::Table,
+            "--format" => {
+                format = match it.next().map(String::as_str) {
+                    Some("table") => Format::Table,
+                    Some("csv") => Format::Csv,
+                    Some("json") => Format::Json,
+                    _ => panic!("Format must be table, csv or json")
+                }
+            },
+            _ if !arg.starts_with("--") => input_filename = arg.clone(),
+            _ => panic!("Syntax : {} [--simple|--recursive] [--report] [--format table|csv|json] [input file]", args[0])
+        }
+    }
+
+    let masses = read_masses(&input_filename);
+    match format {
+        Format::Totals => {
+            for mode in modes {
+                let total = total_fuel(masses.iter().map(|(_, mass)| *mass), mode);
+                match mode {
+                    Mode::Simple => println!("total: {}", total),
+                    Mode::Recursive => println!("Fuel with fuel of fuel: {}", total)
+                }
+            }
+        },
+        Format::Table => println!("{}", FuelReport::new(&masses)),
+        Format::Csv => print!("{}", FuelReport::new(&masses).to_csv()),
+        Format::Json => println!("{}", FuelReport::new(&masses).to_json())
+    }
+}
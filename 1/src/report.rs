@@ -0,0 +1,165 @@
+use std::fmt;
+
+use crate::{compute_fuel, fuel_chain};
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct ModuleFuel {
+    pub line: usize,
+    pub mass: u64,
+    pub direct_fuel: u64,
+    pub recursive_fuel: u64,
+    pub iterations: u64
+}
+
+impl ModuleFuel {
+    pub fn new(line: usize, mass: u64) -> ModuleFuel {
+        let (recursive_fuel, iterations) = fuel_chain(mass).fold((0, 0), |(total, count), fuel| (total + fuel, count + 1));
+        ModuleFuel {
+            line,
+            mass,
+            direct_fuel: compute_fuel(mass),
+            recursive_fuel,
+            iterations
+        }
+    }
+
+    fn to_json(self) -> String {
+        format!("{{\"line\":{},\"mass\":{},\"direct_fuel\":{},\"recursive_fuel\":{},\"iterations\":{}}}",
+                self.line, self.mass, self.direct_fuel, self.recursive_fuel, self.iterations)
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Statistics {
+    pub total: u128,
+    pub min: u64,
+    pub max: u64,
+    pub mean: f64
+}
+
+impl Statistics {
+    fn compute<I: Iterator<Item = u64> + Clone>(values: I) -> Option<Statistics> {
+        let total: u128 = values.clone().map(u128::from).sum();
+        Some(Statistics {
+            total,
+            min: values.clone().min()?,
+            max: values.clone().max()?,
+            mean: total as f64 / values.count() as f64
+        })
+    }
+
+    fn to_json(self) -> String {
+        format!("{{\"total\":{},\"min\":{},\"max\":{},\"mean\":{}}}", self.total, self.min, self.max, self.mean)
+    }
+}
+
+const COLUMNS: [&str; 4] = ["mass", "direct_fuel", "recursive_fuel", "iterations"];
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct FuelReport {
+    pub modules: Vec<ModuleFuel>
+}
+
+impl FuelReport {
+    pub fn new(masses: &[(usize, u64)]) -> FuelReport {
+        FuelReport {
+            modules: masses.iter().map(|(line, mass)| ModuleFuel::new(*line, *mass)).collect()
+        }
+    }
+
+    fn column(&self, index: usize) -> impl Iterator<Item = u64> + Clone + '_ {
+        self.modules.iter().map(move |module| match index {
+            0 => module.mass,
+            1 => module.direct_fuel,
+            2 => module.recursive_fuel,
+            _ => module.iterations
+        })
+    }
+
+    // Statistics of mass, direct fuel, recursive fuel and iterations, None
+    // when there is no module.
+    pub fn statistics(&self) -> Option<[Statistics; 4]> {
+        Some([
+            Statistics::compute(self.column(0))?,
+            Statistics::compute(self.column(1))?,
+            Statistics::compute(self.column(2))?,
+            Statistics::compute(self.column(3))?
+        ])
+    }
+
+    // Module rows, then total, min, max and mean rows.
+    pub fn to_csv(&self) -> String {
+        let mut res = format!("line,{}\n", COLUMNS.join(","));
+        for module in &self.modules {
+            res += &format!("{},{},{},{},{}\n", module.line, module.mass, module.direct_fuel, module.recursive_fuel, module.iterations);
+        }
+        if let Some(statistics) = self.statistics() {
+            let row = |name: &str, value: &dyn Fn(&Statistics) -> String| {
+                format!("{},{}\n", name, statistics.iter().map(value).collect::<Vec<String>>().join(","))
+            };
+            res += &row("total", &|s| s.total.to_string());
+            res += &row("min", &|s| s.min.to_string());
+            res += &row("max", &|s| s.max.to_string());
+            res += &row("mean", &|s| format!("{:.2}", s.mean));
+        }
+        res
+    }
+
+    pub fn to_json(&self) -> String {
+        let modules: Vec<String> = self.modules.iter().map(|module| module.to_json()).collect();
+        let statistics = match self.statistics() {
+            Some(statistics) => {
+                let columns: Vec<String> = COLUMNS.iter().zip(statistics.iter())
+                    .map(|(name, s)| format!("\"{}\":{}", name, s.to_json()))
+                    .collect();
+                format!("{{{}}}", columns.join(","))
+            },
+            None => "null".to_string()
+        };
+        format!("{{\"modules\":[{}],\"statistics\":{}}}", modules.join(","), statistics)
+    }
+}
+
+// Table with one row per module and the statistics below.
+impl fmt::Display for FuelReport {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(f, "{:>6} {:>20} {:>20} {:>20} {:>10}", "line", "mass", "direct fuel", "recursive fuel", "iterations")?;
+        for module in &self.modules {
+            writeln!(f, "{:>6} {:>20} {:>20} {:>20} {:>10}", module.line, module.mass, module.direct_fuel, module.recursive_fuel, module.iterations)?;
+        }
+        match self.statistics() {
+            Some([mass, direct, recursive, iterations]) => {
+                writeln!(f, "{:>6} {:>20} {:>20} {:>20} {:>10}", "total", mass.total, direct.total, recursive.total, iterations.total)?;
+                writeln!(f, "{:>6} {:>20} {:>20} {:>20} {:>10}", "min", mass.min, direct.min, recursive.min, iterations.min)?;
+                writeln!(f, "{:>6} {:>20} {:>20} {:>20} {:>10}", "max", mass.max, direct.max, recursive.max, iterations.max)?;
+                write!(f, "{:>6} {:>20.2} {:>20.2} {:>20.2} {:>10.2}", "mean", mass.mean, direct.mean, recursive.mean, iterations.mean)
+            },
+            None => write!(f, "no module")
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_report() {
+        let report = FuelReport::new(&[(1, 14), (2, 1969), (4, 100756)]);
+        assert_eq!(report.modules[1], ModuleFuel { line: 2, mass: 1969, direct_fuel: 654, recursive_fuel: 966, iterations: 5 });
+        let [mass, direct, recursive, _] = report.statistics().unwrap();
+        assert_eq!((mass.min, mass.max, mass.total), (14, 100756, 102739));
+        assert_eq!(direct.total, 2 + 654 + 33583);
+        assert_eq!(recursive.mean, (2 + 966 + 50346) as f64 / 3.);
+        assert_eq!(report.to_csv().lines().nth(2), Some("2,1969,654,966,5"));
+        assert_eq!(report.to_csv().lines().last(), Some("mean,34246.33,11413.00,17104.67,5.00"));
+        assert!(report.to_json().starts_with("{\"modules\":[{\"line\":1,\"mass\":14,\"direct_fuel\":2,\"recursive_fuel\":2,\"iterations\":1},"));
+    }
+
+    #[test]
+    fn test_empty_report() {
+        let report = FuelReport::new(&[]);
+        assert_eq!(report.statistics(), None);
+        assert_eq!(report.to_json(), "{\"modules\":[],\"statistics\":null}");
+    }
+}
@@ -0,0 +1,79 @@
+use std::io::Write;
+
+use crate::Asteroid;
+
+const EMPTY: [u8; 3] = [0, 0, 0];
+const MARK: [u8; 3] = [255, 255, 255];
+
+// From blue for the fewest visible asteroids to red for the most.
+fn color(count: u32, min: u32, max: u32) -> [u8; 3] {
+    let t = if max > min { f64::from(count - min) / f64::from(max - min) } else { 1. };
+    [(255. * t).round() as u8, 48, (255. * (1. - t)).round() as u8]
+}
+
+// Every cell is a scale x scale square, black when there is no asteroid. The
+// cell of best is framed in white.
+pub fn write_heatmap<W: Write>(w: W, width: u32, height: u32, sights: &[(Asteroid, u32)], best: &Asteroid, scale: u32)
+        -> Result<(), png::EncodingError> {
+    let min = sights.iter().map(|(_, count)| *count).min().unwrap_or(0);
+    let max = sights.iter().map(|(_, count)| *count).max().unwrap_or(0);
+    let too_large = || png::EncodingError::Format("heatmap too large".into());
+    let image_width = width.checked_mul(scale).ok_or_else(too_large)?;
+    let image_height = height.checked_mul(scale).ok_or_else(too_large)?;
+    let cell_count = (width as usize).checked_mul(height as usize).ok_or_else(too_large)?;
+    let byte_count = (image_width as usize).checked_mul(image_height as usize)
+        .and_then(|pixels| pixels.checked_mul(3))
+        .ok_or_else(too_large)?;
+    let index = |col: u32, row: u32| row as usize * width as usize + col as usize;
+    let mut cells = vec![EMPTY; cell_count];
+    for (a, count) in sights {
+        cells[index(a.x, a.y)] = color(*count, min, max);
+    }
+
+    let mut rgb = Vec::<u8>::with_capacity(byte_count);
+    for y in 0..image_height {
+        for x in 0..image_width {
+            let (col, row) = (x / scale, y / scale);
+            let on_border = [x % scale, y % scale].iter().any(|offset| *offset == 0 || *offset == scale - 1);
+            let pixel = if col == best.x && row == best.y && on_border { MARK } else { cells[index(col, row)] };
+            rgb.extend_from_slice(&pixel);
+        }
+    }
+    let mut encoder = png::Encoder::new(w, image_width, image_height);
+    encoder.set_color(png::ColorType::RGB);
+    encoder.set_depth(png::BitDepth::Eight);
+    let mut writer = encoder.write_header()?;
+    writer.write_image_data(&rgb)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_heatmap() {
+        let sights = vec![(Asteroid { x: 1, y: 0 }, 2), (Asteroid { x: 0, y: 1 }, 4), (Asteroid { x: 2, y: 1 }, 3)];
+        let mut data = Vec::<u8>::new();
+        write_heatmap(&mut data, 3, 2, &sights, &Asteroid { x: 0, y: 1 }, 4).unwrap();
+
+        let (info, mut reader) = png::Decoder::new(data.as_slice()).read_info().unwrap();
+        assert_eq!((info.width, info.height, info.color_type), (12, 8, png::ColorType::RGB));
+        let mut rgb = vec![0; info.buffer_size()];
+        reader.next_frame(&mut rgb).unwrap();
+        let pixel = |x: usize, y: usize| [rgb[(y * 12 + x) * 3], rgb[(y * 12 + x) * 3 + 1], rgb[(y * 12 + x) * 3 + 2]];
+        assert_eq!(pixel(1, 1), EMPTY);
+        assert_eq!(pixel(5, 2), [0, 48, 255]);
+        assert_eq!(pixel(9, 6), [128, 48, 128]);
+        assert_eq!(pixel(0, 4), MARK);
+        assert_eq!(pixel(3, 7), MARK);
+        assert_eq!(pixel(2, 6), [255, 48, 0]);
+    }
+
+    #[test]
+    fn test_heatmap_too_large() {
+        let mut data = Vec::<u8>::new();
+        let result = write_heatmap(&mut data, u32::MAX, 2, &[], &Asteroid { x: 0, y: 0 }, 2);
+        assert!(matches!(result, Err(png::EncodingError::Format(_))));
+        assert!(data.is_empty());
+    }
+}
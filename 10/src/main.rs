@@ -1,13 +1,13 @@
 use std::env;
 use std::fmt;
 use std::fs::File;
-use std::io::{BufRead, BufReader};
+use std::io::{BufRead, BufReader, BufWriter};
 
-extern crate cgmath;
+use std::cmp::Ordering;
+use std::convert::TryFrom;
+use std::collections::{HashMap, HashSet};
 
-use core::ops::Neg;
-use cgmath::*;
-use cgmath::Vector2;
+mod heatmap;
 
 #[derive(Clone,Copy)]
 struct Asteroid {
@@ -21,30 +21,126 @@ impl PartialEq for Asteroid {
     }
 }
 
-#[derive(Clone)]
-struct AsteroidField {
-    asteroids: Vec<Asteroid>,
-    canon_index: usize,
-    canon_direction: Vector2::<f64>,
-    former_angle: Option<f64>
+// Displacement divided by the gcd of its coordinates: asteroids hide each
+// other exactly when they share a direction. y grows downwards.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+struct Direction {
+    dx: i64,
+    dy: i64
+}
+
+fn gcd(a: i64, b: i64) -> i64 {
+    if b == 0 { a.abs() } else { gcd(b, a % b) }
+}
+
+impl Direction {
+    const UP: Direction = Direction { dx: 0, dy: -1 };
+
+    fn new(dx: i64, dy: i64) -> Option<Direction> {
+        let g = gcd(dx, dy);
+        if g == 0 { None } else { Some(Direction { dx: dx / g, dy: dy / g }) }
+    }
+
+    // Direction and number of steps along it.
+    fn between(from: &Asteroid, to: &Asteroid) -> (Direction, i64) {
+        let dx = i64::from(to.x) - i64::from(from.x);
+        let dy = i64::from(to.y) - i64::from(from.y);
+        let g = gcd(dx, dy);
+        (Direction { dx: dx / g, dy: dy / g }, g)
+    }
+
+    // Positive when other is less than a half turn clockwise from self, y
+    // growing downwards.
+    fn cross(&self, other: &Direction) -> i64 {
+        self.dx * other.dy - self.dy * other.dx
+    }
+
+    // 0 from start included to its opposite excluded, clockwise, 1 for the
+    // other half.
+    fn half(&self, start: &Direction) -> u8 {
+        let cross = start.cross(self);
+        let dot = start.dx * self.dx + start.dy * self.dy;
+        if cross > 0 || (cross == 0 && dot > 0) { 0 } else { 1 }
+    }
+
+    // Clockwise order starting from start. Within a half turn, a comes before
+    // b when their cross product is positive.
+    fn cmp_from(&self, other: &Direction, start: &Direction) -> Ordering {
+        self.half(start).cmp(&other.half(start))
+            .then_with(|| 0.cmp(&self.cross(other)))
+    }
+
+    // Reflected on the vertical axis, clockwise order becoming counterclockwise.
+    fn mirrored(&self) -> Direction {
+        Direction { dx: -self.dx, dy: self.dy }
+    }
+}
+
+// Clockwise order starting from up.
+impl Ord for Direction {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.cmp_from(other, &Direction::UP)
+    }
+}
+
+impl PartialOrd for Direction {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+enum Rotation {
+    Clockwise,
+    Counterclockwise
 }
 
-impl Asteroid {
-    fn displacement(&self, other: &Asteroid) -> Vector2<f64> {
-        Vector2::<f64>::new(other.x as f64- self.x as f64,other.y as f64 - self.y as f64)
+// Direction the laser first points to, and sense it rotates in.
+#[derive(Clone, Copy, Debug)]
+struct Laser {
+    start: Direction,
+    rotation: Rotation
+}
+
+impl Default for Laser {
+    fn default() -> Laser {
+        Laser { start: Direction::UP, rotation: Rotation::Clockwise }
     }
 }
 
+impl Laser {
+    // Order in which the laser sweeps directions.
+    fn cmp(&self, a: &Direction, b: &Direction) -> Ordering {
+        match self.rotation {
+            Rotation::Clockwise => a.cmp_from(b, &self.start),
+            Rotation::Counterclockwise => a.mirrored().cmp_from(&b.mirrored(), &self.start.mirrored())
+        }
+    }
+}
+
+// The station may be put anywhere, on an asteroid or not.
+#[derive(Clone)]
+struct AsteroidField {
+    asteroids: Vec<Asteroid>,
+    width: u32,
+    height: u32,
+    station: Asteroid
+}
+
 impl AsteroidField {
     pub fn load_file(filename: &str) -> AsteroidField {
-        let mut asteroids = Vec::<Asteroid>::new();        
+        let mut asteroids = Vec::<Asteroid>::new();
+        let mut width = 0;
+        let mut height = 0;
         let file = File::open(filename).unwrap();
         let reader = BufReader::new(file);
         for (y, line) in reader.lines().enumerate() {
             let line = line.unwrap();
             if !line.trim().is_empty() {
+                width = width.max(line.len() as u32);
+                height = y as u32 + 1;
                 for (x, ch) in line.as_bytes().iter().enumerate() {
-                    if *ch == '#' as u8  || *ch == 'X' as u8 {
+                    if *ch == b'#' || *ch == b'X' {
                         asteroids.push(Asteroid {
                             x: x as u32,
                             y: y as u32
@@ -56,98 +152,146 @@ impl AsteroidField {
 
         let mut ret = AsteroidField {
             asteroids,
-            canon_index : 0,
-            canon_direction: Vector2::<f64> { x: 0., y: -1. },
-            former_angle: None
+            width,
+            height,
+            station: Asteroid { x: 0, y: 0 }
         };
-        let best = ret.find_best_asteroid();
-        ret.canon_index = ret.asteroids.iter().position(|&aster| aster == best.0).unwrap();
+        if !ret.asteroids.is_empty() {
+            ret.station = ret.find_best_asteroid().0;
+        }
         ret
     }
 
-    fn compute_others_in_sight(&self) -> Vec<(Asteroid,u32)> {
-        let mut ret = Vec::<(Asteroid,u32)>::new();
-        let ref_vector = Vector2::<f64> {
-            x: 5.,
-            y: 0.
-        };
+    // Same asteroids, with the station moved.
+    fn with_station(&self, station: Asteroid) -> AsteroidField {
+        AsteroidField { station, ..self.clone() }
+    }
 
-        for a in &self.asteroids {
-            let mut displacements = Vec::<Vector2::<f64>>::new();
-            for b in &self.asteroids {
-                if b == a {
-                    continue;
-                }
-                displacements.push(a.displacement(&b));
-            }
-            let mut angles: Vec<Rad<f64>> = displacements.iter().map(|d| d.angle(ref_vector)).collect();
-            angles.sort_by(|a, b| a.partial_cmp(b).unwrap());
-            angles.dedup();
-            ret.push((*a,angles.len() as u32));
-        }
-        ret
+    fn visible_from(&self, position: &Asteroid) -> u32 {
+        let directions: HashSet<Direction> = self.asteroids.iter()
+            .filter(|b| *b != position)
+            .map(|b| Direction::between(position, b).0)
+            .collect();
+        directions.len() as u32
     }
+
+    fn compute_others_in_sight(&self) -> Vec<(Asteroid,u32)> {
+        self.asteroids.iter().map(|a| (*a, self.visible_from(a))).collect()
+    }
+
     fn find_best_asteroid(&self) -> (Asteroid, u32) {
         let sights = self.compute_others_in_sight();
         *sights.iter().max_by(|x, y| x.1.cmp(&y.1)).unwrap()
     }
 
-    fn shoot_asteroid(&mut self) -> Asteroid {
-        let mut displacements = Vec::<(Asteroid,Vector2::<f64>)>::new();
-        let canon_position = self.asteroids[self.canon_index];
-        for a in &self.asteroids {
-            if *a == canon_position {
-                continue;
-            }
-            displacements.push((*a,canon_position.displacement(&a)));
+    // Asteroids in the order the laser of the station vaporizes them:
+    // bucketed per direction, nearest last, buckets sorted as swept.
+    fn vaporization_order(&self, laser: &Laser) -> Vaporization {
+        let mut buckets = HashMap::<Direction, Vec<(i64, Asteroid)>>::new();
+        for a in self.asteroids.iter().filter(|a| **a != self.station) {
+            let (direction, steps) = Direction::between(&self.station, a);
+            buckets.entry(direction).or_default().push((steps, *a));
         }
-
-        let mut angles_distances: Vec<(Asteroid,f64,f64)> = 
-            displacements.iter().map(|d| (d.0, AsteroidField::modulo(&d.1.angle(self.canon_direction).neg().s), d.1.length()))
-            .collect();
-        angles_distances.sort_by(|a, b| { 
-            if a.1.eq(&b.1) {
-                return a.2.partial_cmp(&b.2).unwrap();
-            } else {
-                return a.1.partial_cmp(&b.1).unwrap();
-            }
-        });
-        angles_distances.reverse();
-        match self.former_angle {
-            Some(x) => {
-                let mut angles_only: Vec<f64> = angles_distances.iter().map(|(aster, angle, distance)| *angle).collect();
-                angles_only.dedup();
-                if angles_only.len() > 1 { angles_distances.retain(|(aster, angle, distance)| angle.abs() > 1e-4) }
-            },
-            None => { }
+        let mut buckets: Vec<(Direction, Vec<(i64, Asteroid)>)> = buckets.into_iter().collect();
+        buckets.sort_unstable_by(|(a, _), (b, _)| laser.cmp(a, b));
+        Vaporization {
+            buckets: buckets.into_iter().map(|(_, mut bucket)| {
+                bucket.sort_unstable_by_key(|(steps, _)| std::cmp::Reverse(*steps));
+                bucket.into_iter().map(|(_, a)| a).collect()
+            }).collect(),
+            index: 0
         }
-        let shooted = angles_distances.pop().unwrap();
-        self.former_angle = Some(shooted.1);
-        let shooted = shooted.0;
-        self.canon_direction = canon_position.displacement(&shooted);
-        self.asteroids.retain(|i| *i != shooted);
-        self.canon_index = self.asteroids.iter().position(|&aster| aster == canon_position).unwrap();
-        shooted
-    }
-
-    fn shoot_all_asteroids(&mut self) -> Vec<Asteroid> {
-        let mut ret = Vec::<Asteroid>::new();
-        while self.asteroids.len() > 1 {
-            ret.push(self.shoot_asteroid());
+    }
+}
+
+// One asteroid per bucket and per rotation. Emptied buckets are dropped at the
+// end of each rotation, so every asteroid costs O(1).
+struct Vaporization {
+    buckets: Vec<Vec<Asteroid>>,
+    index: usize
+}
+
+impl Iterator for Vaporization {
+    type Item = Asteroid;
+
+    fn next(&mut self) -> Option<Asteroid> {
+        if self.index == self.buckets.len() {
+            self.buckets.retain(|bucket| !bucket.is_empty());
+            self.index = 0;
         }
-        ret
+        let bucket = self.buckets.get_mut(self.index)?;
+        self.index += 1;
+        bucket.pop()
     }
+}
 
-    fn modulo(param: &f64) -> f64 {
-        let TWO_PI = 2.*std::f64::consts::PI;
-        if *param > TWO_PI {
-            return *param - TWO_PI;
-        } else if *param < 0. {
-            return *param + TWO_PI;
-        } else {
-            return *param;
+impl fmt::Debug for Asteroid {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "({}, {})", self.x, self.y)
+    }
+}
+
+impl fmt::Display for Asteroid {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "({}, {})", self.x, self.y)
+    }
+}
+
+fn parse_pair(value: &str) -> Option<(i64, i64)> {
+    let (x, y) = value.split_once(',')?;
+    Some((x.trim().parse().ok()?, y.trim().parse().ok()?))
+}
+
+fn main() {
+    let args:Vec<String> = env::args().collect();
+    let mut input_file: Option<String> = None;
+    let mut station: Option<Asteroid> = None;
+    let mut laser = Laser::default();
+    let mut heatmap_file: Option<String> = None;
+    let mut scale = 8;
+    let mut it = args.iter().skip(1);
+    while let Some(arg) = it.next() {
+        let mut value = || it.next().unwrap_or_else(|| panic!("Missing value for {}", arg)).as_str();
+        match arg.as_str() {
+            "--station" => {
+                let position = parse_pair(value())
+                    .and_then(|(x, y)| Some(Asteroid { x: u32::try_from(x).ok()?, y: u32::try_from(y).ok()? }));
+                station = Some(position.unwrap_or_else(|| panic!("Station must be x,y with 0 <= x, y <= {}", u32::MAX)));
+            },
+            "--start" => laser.start = parse_pair(value()).and_then(|(dx, dy)| Direction::new(dx, dy)).expect("Start direction must be dx,dy, not 0,0"),
+            "--counterclockwise" => laser.rotation = Rotation::Counterclockwise,
+            "--heatmap" => heatmap_file = Some(value().to_string()),
+            "--scale" => scale = value().parse::<u32>().ok().filter(|scale| *scale > 0).expect("Invalid scale"),
+            _ if !arg.starts_with("--") => input_file = Some(arg.clone()),
+            _ => panic!("Unknown option {}", arg)
         }
     }
+    let input_file = input_file.unwrap_or_else(|| panic!("Syntax : {} [--station x,y] [--start dx,dy] [--counterclockwise] [--heatmap <png file>] [--scale <pixels>] <input file>", args[0]));
+    let asteroid_field = AsteroidField::load_file(&input_file);
+    // the station of a loaded field is the best asteroid
+    let result = (asteroid_field.station, asteroid_field.visible_from(&asteroid_field.station));
+    if asteroid_field.asteroids.is_empty() {
+        println!("part1 : no asteroids");
+    } else {
+        println!("part1 : {:?}", result);
+    }
+    let asteroid_field = match station {
+        Some(station) => {
+            println!("station {} sees {} asteroids", station, asteroid_field.visible_from(&station));
+            asteroid_field.with_station(station)
+        },
+        None => asteroid_field
+    };
+    match asteroid_field.vaporization_order(&laser).nth(199) {
+        Some(shooted) => println!("part2 : {:?}", shooted.x*100+shooted.y),
+        None => println!("part2 : less than 200 asteroids")
+    }
+    if let Some(heatmap_file) = heatmap_file {
+        let file = File::create(&heatmap_file).unwrap_or_else(|_| panic!("Something went wrong creating {}", heatmap_file));
+        let sights = asteroid_field.compute_others_in_sight();
+        heatmap::write_heatmap(BufWriter::new(file), asteroid_field.width, asteroid_field.height, &sights, &result.0, scale)
+            .unwrap_or_else(|err| panic!("Something went wrong writing {}: {}", heatmap_file, err));
+    }
 }
 
 #[cfg(test)]
@@ -202,71 +346,103 @@ mod tests {
         assert_eq!(best, (Asteroid { x: 11, y: 13}, 210));
     }
 
+    #[test]
+    fn direction_order() {
+        let directions: Vec<Direction> = [(0, -1), (1, -3), (1, -1), (3, -1), (1, 0), (1, 2), (0, 1), (-1, 1), (-1, 0), (-2, -1), (-1, -5)]
+            .iter().map(|(dx, dy)| Direction { dx: *dx, dy: *dy }).collect();
+        let mut sorted = directions.clone();
+        sorted.sort_by(|a, b| b.cmp(a));
+        sorted.sort();
+        assert_eq!(sorted, directions);
+        let laser = Laser { start: Direction::UP, rotation: Rotation::Counterclockwise };
+        sorted.sort_by(|a, b| laser.cmp(a, b));
+        let expected: Vec<Direction> = [0, 10, 9, 8, 7, 6, 5, 4, 3, 2, 1].iter().map(|index| directions[*index]).collect();
+        assert_eq!(sorted, expected);
+        let laser = Laser { start: Direction::new(2, 0).unwrap(), rotation: Rotation::Clockwise };
+        sorted.sort_by(|a, b| laser.cmp(a, b));
+        let expected: Vec<Direction> = [4, 5, 6, 7, 8, 9, 10, 0, 1, 2, 3].iter().map(|index| directions[*index]).collect();
+        assert_eq!(sorted, expected);
+        let (direction, steps) = Direction::between(&Asteroid { x: 7, y: 9 }, &Asteroid { x: 1, y: 1 });
+        assert_eq!((direction, steps), (Direction { dx: -3, dy: -4 }, 2));
+    }
+
+    #[test]
+    fn vaporize_large() {
+        let asteroid_field: AsteroidField = AsteroidField::load_file("test5.in");
+        let shoot_all: Vec<Asteroid> = asteroid_field.vaporization_order(&Laser::default()).collect();
+        assert_eq!(shoot_all.len(), 299);
+        assert_eq!(shoot_all[0], Asteroid { x: 11, y: 12 });
+        assert_eq!(shoot_all[1], Asteroid { x: 12, y: 1 });
+        assert_eq!(shoot_all[2], Asteroid { x: 12, y: 2 });
+        assert_eq!(shoot_all[9], Asteroid { x: 12, y: 8 });
+        assert_eq!(shoot_all[19], Asteroid { x: 16, y: 0 });
+        assert_eq!(shoot_all[49], Asteroid { x: 16, y: 9 });
+        assert_eq!(shoot_all[99], Asteroid { x: 10, y: 16 });
+        assert_eq!(shoot_all[198], Asteroid { x: 9, y: 6 });
+        assert_eq!(shoot_all[199], Asteroid { x: 8, y: 2 });
+        assert_eq!(shoot_all[200], Asteroid { x: 10, y: 9 });
+        assert_eq!(shoot_all[298], Asteroid { x: 11, y: 1 });
+    }
+
     #[test]
     fn vaporizei6() {
-        let mut asteroid_field: AsteroidField = AsteroidField::load_file("test6.in");
-        assert_eq!(asteroid_field.asteroids[asteroid_field.canon_index], Asteroid { x: 8, y:3 });
-        assert_eq!(asteroid_field.shoot_asteroid(), Asteroid { x: 8, y:1 });
-        assert_eq!(asteroid_field.shoot_asteroid(), Asteroid { x: 9, y:0 });
-        assert_eq!(asteroid_field.shoot_asteroid(), Asteroid { x: 9, y:1 });
-        assert_eq!(asteroid_field.shoot_asteroid(), Asteroid { x: 10, y:0 });
-        assert_eq!(asteroid_field.shoot_asteroid(), Asteroid { x: 9, y:2 });
-        assert_eq!(asteroid_field.shoot_asteroid(), Asteroid { x: 11, y:1 });
-        assert_eq!(asteroid_field.shoot_asteroid(), Asteroid { x: 12, y:1 });
-        assert_eq!(asteroid_field.shoot_asteroid(), Asteroid { x: 11, y:2 });
-        assert_eq!(asteroid_field.shoot_asteroid(), Asteroid { x: 15, y:1 });
-        assert_eq!(asteroid_field.shoot_asteroid(), Asteroid { x: 12, y:2 });
-        assert_eq!(asteroid_field.shoot_asteroid(), Asteroid { x: 13, y:2 });
-        assert_eq!(asteroid_field.shoot_asteroid(), Asteroid { x: 14, y:2 });
-        assert_eq!(asteroid_field.shoot_asteroid(), Asteroid { x: 15, y:2 });
-        assert_eq!(asteroid_field.shoot_asteroid(), Asteroid { x: 12, y:3 });
-        assert_eq!(asteroid_field.shoot_asteroid(), Asteroid { x: 16, y:4 });
-        assert_eq!(asteroid_field.shoot_asteroid(), Asteroid { x: 15, y:4 });
-        assert_eq!(asteroid_field.shoot_asteroid(), Asteroid { x: 10, y:4 });
-        assert_eq!(asteroid_field.shoot_asteroid(), Asteroid { x: 4, y:4 });
-        assert_eq!(asteroid_field.shoot_asteroid(), Asteroid { x: 2, y:4 });
-        assert_eq!(asteroid_field.shoot_asteroid(), Asteroid { x: 2, y:3 });
-        assert_eq!(asteroid_field.shoot_asteroid(), Asteroid { x: 0, y:2 });
-        assert_eq!(asteroid_field.shoot_asteroid(), Asteroid { x: 1, y:2 });
-        assert_eq!(asteroid_field.shoot_asteroid(), Asteroid { x: 0, y:1 });
-        assert_eq!(asteroid_field.shoot_asteroid(), Asteroid { x: 1, y:1 });
-        assert_eq!(asteroid_field.shoot_asteroid(), Asteroid { x: 5, y:2 });
-        assert_eq!(asteroid_field.shoot_asteroid(), Asteroid { x: 1, y:0 });
-        assert_eq!(asteroid_field.shoot_asteroid(), Asteroid { x: 5, y:1 });
-        assert_eq!(asteroid_field.shoot_asteroid(), Asteroid { x: 6, y:1 });
-        assert_eq!(asteroid_field.shoot_asteroid(), Asteroid { x: 6, y:0 });
-        assert_eq!(asteroid_field.shoot_asteroid(), Asteroid { x: 7, y:0 });
-        assert_eq!(asteroid_field.shoot_asteroid(), Asteroid { x: 8, y:0 });
-        assert_eq!(asteroid_field.shoot_asteroid(), Asteroid { x: 10, y:1 });
-        assert_eq!(asteroid_field.shoot_asteroid(), Asteroid { x: 14, y:0 });
-        assert_eq!(asteroid_field.shoot_asteroid(), Asteroid { x: 16, y:1 });
-        assert_eq!(asteroid_field.shoot_asteroid(), Asteroid { x: 13, y:3 });
-        assert_eq!(asteroid_field.shoot_asteroid(), Asteroid { x: 14, y:3 });
+        let asteroid_field: AsteroidField = AsteroidField::load_file("test6.in");
+        assert_eq!(asteroid_field.station, Asteroid { x: 8, y:3 });
+        let order: Vec<(u32,u32)> = asteroid_field.vaporization_order(&Laser::default()).map(|a| (a.x, a.y)).collect();
+        assert_eq!(order, [(8, 1), (9, 0), (9, 1), (10, 0), (9, 2), (11, 1), (12, 1), (11, 2), (15, 1), (12, 2), (13, 2), (14, 2), (15, 2), (12, 3), (16, 4), (15, 4), (10, 4), (4, 4), (2, 4), (2, 3), (0, 2), (1, 2), (0, 1), (1, 1), (5, 2), (1, 0), (5, 1), (6, 1), (6, 0), (7, 0), (8, 0), (10, 1), (14, 0), (16, 1), (13, 3), (14, 3)]);
     }
-}
 
-impl fmt::Debug for Asteroid {
-    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        write!(f, "({}, {})", self.x, self.y)
+    #[test]
+    fn vaporize_custom_station() {
+        let asteroid_field: AsteroidField = AsteroidField::load_file("test6.in");
+        let default_order: Vec<Asteroid> = asteroid_field.vaporization_order(&Laser::default()).collect();
+        let moved = asteroid_field.with_station(Asteroid { x: 0, y: 4 });
+        assert_eq!(asteroid_field.station, Asteroid { x: 8, y: 3 });
+        let laser = Laser { start: Direction::new(1, 0).unwrap(), rotation: Rotation::Counterclockwise };
+        let order: Vec<Asteroid> = moved.vaporization_order(&laser).collect();
+        assert_eq!(order.len(), default_order.len() + 1);
+        assert_ne!(order[..default_order.len()], default_order[..]);
+        assert_eq!(order[..3], [Asteroid { x: 2, y: 4 }, Asteroid { x: 14, y: 3 }, Asteroid { x: 13, y: 3 }]);
+        assert_eq!(order, reference_order(&moved, &laser));
     }
-}
 
-impl fmt::Display for Asteroid {
-    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        write!(f, "({}, {})", self.x, self.y)
+    // Rank of each asteroid: number of asteroids hiding it. Rotations then
+    // follow the clockwise order of directions.
+    fn reference_order(asteroid_field: &AsteroidField, laser: &Laser) -> Vec<Asteroid> {
+        let mut others: Vec<(Direction, i64, Asteroid)> = asteroid_field.asteroids.iter()
+            .filter(|a| **a != asteroid_field.station)
+            .map(|a| {
+                let (direction, steps) = Direction::between(&asteroid_field.station, a);
+                (direction, steps, *a)
+            })
+            .collect();
+        others.sort_by_key(|(direction, steps, _)| (*direction, *steps));
+        let mut ranked = Vec::<(usize, Direction, Asteroid)>::new();
+        for (direction, _, a) in others.iter() {
+            let rank = match ranked.last() {
+                Some((rank, previous, _)) if previous == direction => rank + 1,
+                _ => 0
+            };
+            ranked.push((rank, *direction, *a));
+        }
+        ranked.sort_by(|(rank, a, _), (other_rank, b, _)| rank.cmp(other_rank).then_with(|| laser.cmp(a, b)));
+        ranked.into_iter().map(|(_, _, a)| a).collect()
     }
-}
 
-fn main() {
-    let args:Vec<String> = env::args().collect();
-    if args.len() < 2 {
-        panic!("Syntax : {} <inputi file>",args[0]);
-    }
-    let input_file = &args[1];
-    let mut asteroid_field = AsteroidField::load_file(&input_file);
-    let result = asteroid_field.find_best_asteroid();
-    println!("part1 : {:?}", result);
-    let shoot_all = asteroid_field.shoot_all_asteroids();
-    let result = shoot_all[199].x*100+shoot_all[199].y;
-    println!("part2 : {:?}", result);
+    #[test]
+    fn vaporize_10k() {
+        let asteroids: Vec<Asteroid> = (0..100).flat_map(|y| (0..100).map(move |x| Asteroid { x, y })).collect();
+        let mut asteroid_field = AsteroidField { asteroids, width: 100, height: 100, station: Asteroid { x: 61, y: 37 } };
+        let laser = Laser::default();
+        let order: Vec<Asteroid> = asteroid_field.vaporization_order(&laser).collect();
+        assert_eq!(order.len(), 9_999);
+        assert_eq!(order, reference_order(&asteroid_field, &laser));
+        assert_eq!(asteroid_field.vaporization_order(&laser).nth(199), Some(order[199]));
+        // off the field, every asteroid is vaporized
+        asteroid_field.station = Asteroid { x: 130, y: 20 };
+        let laser = Laser { start: Direction::new(-3, 1).unwrap(), rotation: Rotation::Counterclockwise };
+        let order: Vec<Asteroid> = asteroid_field.vaporization_order(&laser).collect();
+        assert_eq!(order.len(), 10_000);
+        assert_eq!(order, reference_order(&asteroid_field, &laser));
+    }
 }
@@ -20,10 +20,10 @@ impl Processor {
         let mut input = inputs.iter();
         loop {
             let full_op_code: i64 = self.memory[Processor::get_from_cursor(&mut self.cur) as usize];
-            let full_op_code: Vec<u8> = format!("{:05}", full_op_code).to_string().as_bytes().iter().map(|b| (b - '0' as u8) as u8).collect();
+            let full_op_code: Vec<u8> = format!("{:05}", full_op_code).to_string().as_bytes().iter().map(|b| b - b'0').collect();
             let mut op_code = full_op_code[full_op_code.len()-1];
             if full_op_code[full_op_code.len()-2] == 9 {
-                op_code = op_code+90;
+                op_code += 90;
             }
             let op_code = op_code;
             let mode_1st = full_op_code[full_op_code.len()-3];
@@ -48,21 +48,20 @@ impl Processor {
                 },
                 4 => {
                     let param1: i64 = self.memory[Processor::get_from_cursor(&mut self.cur) as usize];
-                    let output;
-                    match mode_1st {
+                    let output = match mode_1st {
                         0 => {
-                            output = self.memory[param1 as usize];
+                            self.memory[param1 as usize]
                         },
                         1 => {
-                            output = param1;
+                            param1
                         },
                         2 => {
-                            output = self.memory[(param1+self.relative_offset as i64) as usize];
+                            self.memory[(param1+self.relative_offset as i64) as usize]
                         },
                         _ => {
                             panic!("Unsupported mode");
                         }
-                    }
+                    };
                     return Some(output);
                 },
                 5 => {
@@ -106,7 +105,7 @@ impl Processor {
                 99 => {
                     return None;
                 },
-                _ => panic!(format!("unknown op code: {}", op_code))
+                _ => panic!("unknown op code: {}", op_code)
             }
         }
     }
@@ -114,7 +113,7 @@ impl Processor {
 
     fn read_input_vector(input_filename: &str) -> Vec<i64> {
         let content = fs::read_to_string(input_filename)
-            .expect(&format!("Something went wrong reading {}", input_filename));
+            .unwrap_or_else(|_| panic!("Something went wrong reading {}", input_filename));
         let input_vector: Vec<i64> = content.split(",").map(str::parse::<i64>).filter_map(Result::ok).collect();
         input_vector
     }
@@ -122,13 +121,13 @@ impl Processor {
     fn translate_offset(&self, mode: u8, offset: i64) -> i64 {
         match mode {
             0 => {
-                return self.memory[offset as usize];
+                self.memory[offset as usize]
             },
             1 => {
-                return offset;
+                offset
             },
             2 => {
-                return self.relative_offset as i64 + self.memory[offset as usize];
+                self.relative_offset as i64 + self.memory[offset as usize]
             },
             _ => {
                 panic!("Unhandled mode");
@@ -137,18 +136,17 @@ impl Processor {
     }
 
     fn assign_offset(&mut self, mode: u8, offset: i64, value: i64) {
-        let real_offset: usize;
-        match mode {
+        let real_offset: usize = match mode {
             0 => {
-                real_offset = offset as usize;
+                offset as usize
             },
             2 => {
-                real_offset = (self.relative_offset as i64 + offset) as usize;
+                (self.relative_offset as i64 + offset) as usize
             },
             _ => {
                 panic!("Unhandled mode");
             }
-        }
+        };
         if real_offset >= self.memory.len() {
             self.memory.resize(real_offset+1, 0);
         }
@@ -167,7 +165,7 @@ impl Processor {
 
     fn get_from_cursor(cursor: &mut u32) -> u32 {
         let ret = *cursor;
-        *cursor = *cursor+1;
+        *cursor += 1;
         ret
     }
 }
@@ -4,8 +4,13 @@ use std::io::BufWriter;
 
 mod intcode_computer;
 use intcode_computer::Processor;
+// The OCR is day 8's module, compiled in this crate: moving or renaming
+// 8/src/ocr.rs breaks the day 11 build.
+#[path = "../../8/src/ocr.rs"]
+mod ocr;
 
 #[derive(Copy,Clone)]
+#[allow(clippy::upper_case_acronyms)]
 enum Direction {
     UP = 0,
     LEFT = 1,
@@ -40,9 +45,9 @@ impl Robot {
 
     fn get_position_color_code(&mut self) -> i64 {
         if self.whites.contains(&self.position) {
-            return 1;
+            1
         } else {
-            return 0;
+            0
         }
     }
 
@@ -76,7 +81,7 @@ impl Robot {
     fn turn_right(&mut self) {
         let mut new_direction = Robot::direction_to_i32(self.direction) - 1;
         if new_direction < 0 {
-            new_direction = new_direction + 4;
+            new_direction += 4;
         }
         self.direction = Robot::i32_to_direction(new_direction);
     }
@@ -84,7 +89,7 @@ impl Robot {
     fn turn_left(&mut self) {
         let mut new_direction = Robot::direction_to_i32(self.direction) + 1;
         if new_direction > 3 {
-            new_direction = new_direction - 4;
+            new_direction -= 4;
         }
         self.direction = Robot::i32_to_direction(new_direction);
     }
@@ -119,6 +124,12 @@ impl Robot {
         }
     }
 
+    fn read_code(&self) -> Result<String, ocr::OcrError> {
+        // y grows upwards for the robot, downwards for the OCR
+        let points = self.whites.iter().map(|p| (p.x, -p.y)).collect();
+        ocr::recognize_points(&points)
+    }
+
     fn output_image(&mut self, filename: &str) {
         let min_x = self.visited.iter().map(|v| v.x).min().unwrap();
         let max_x = self.visited.iter().map(|v| v.x).max().unwrap();
@@ -134,7 +145,7 @@ impl Robot {
         }
 
         let file = File::create(filename).unwrap();
-        let ref mut w = BufWriter::new(file);
+        let w = &mut BufWriter::new(file);
         let mut encoder = png::Encoder::new(w, width, height);
         encoder.set_color(png::ColorType::Grayscale);
         let mut writer = encoder.write_header().unwrap();
@@ -154,7 +165,10 @@ fn part2() {
     robot.execute_program("input.txt");
     let result_file_name = "result.png";
     robot.output_image(result_file_name);
-    println!("Code is in {}", result_file_name);
+    match robot.read_code() {
+        Ok(code) => println!("Code is {} (image in {})", code, result_file_name),
+        Err(err) => println!("Code is in {}, could not read it: {}", result_file_name, err)
+    }
 }
 
 fn main() {
@@ -0,0 +1,107 @@
+use std::ops::RangeInclusive;
+
+use rayon::prelude::*;
+
+pub mod intcode_computer;
+pub mod symbolic;
+
+use intcode_computer::{Processor, RunError};
+
+// Instructions after which goal_seek gives up a candidate, which most likely
+// loops forever.
+pub const MAX_STEPS: u64 = 1_000_000;
+
+pub fn run_with_patches(processor: &Processor, patches: &[(usize, i64)], output_cell: usize) -> i64 {
+    let mut processor = processor.clone();
+    for (address, value) in patches {
+        processor.patch(*address, *value);
+    }
+    processor.process(&vec!());
+    processor.memory().get(output_cell).copied().unwrap_or(0)
+}
+
+// Like run_with_patches, but a program failing or still running after
+// max_steps instructions is reported as an error.
+pub fn try_with_patches(processor: &Processor, patches: &[(usize, i64)], output_cell: usize, max_steps: u64) -> Result<i64, RunError> {
+    let mut processor = processor.clone();
+    for (address, value) in patches {
+        processor.patch(*address, *value);
+    }
+    processor.run(&[], Some(max_steps))?;
+    Ok(processor.memory().get(output_cell).copied().unwrap_or(0))
+}
+
+// Tries every combination of values for the patched addresses and returns the
+// lexicographically smallest one leaving target in output_cell. The values of
+// the first address are searched in parallel. Candidates making the program
+// fail or run more than MAX_STEPS instructions do not match.
+pub fn goal_seek(processor: &Processor, addresses: &[usize], values: RangeInclusive<i64>, output_cell: usize, target: i64) -> Option<Vec<i64>> {
+    if addresses.is_empty() || values.is_empty() {
+        return None;
+    }
+    values.clone().into_par_iter().find_map_first(|first| {
+        let mut candidate = vec![*values.start(); addresses.len()];
+        candidate[0] = first;
+        loop {
+            let patches: Vec<(usize, i64)> = addresses.iter().copied().zip(candidate.iter().copied()).collect();
+            if try_with_patches(processor, &patches, output_cell, MAX_STEPS) == Ok(target) {
+                return Some(candidate);
+            }
+            if !next_candidate(&mut candidate[1..], &values) {
+                return None;
+            }
+        }
+    })
+}
+
+fn next_candidate(candidate: &mut [i64], values: &RangeInclusive<i64>) -> bool {
+    for value in candidate.iter_mut().rev() {
+        if *value < *values.end() {
+            *value += 1;
+            return true;
+        }
+        *value = *values.start();
+    }
+    false
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_run_with_patches() {
+        let processor = "1,0,0,0,99".parse::<Processor>().unwrap();
+        assert_eq!(run_with_patches(&processor, &[], 0), 2);
+        assert_eq!(run_with_patches(&processor, &[(1, 4), (2, 4)], 0), 198);
+        assert_eq!(run_with_patches(&processor, &[(7, 5)], 7), 5);
+    }
+
+    #[test]
+    fn test_goal_seek() {
+        let processor = "1,0,0,0,99,10,20,30,40".parse::<Processor>().unwrap();
+        assert_eq!(goal_seek(&processor, &[1, 2], 0..=8, 0, 50), Some(vec![5, 8]));
+        assert_eq!(goal_seek(&processor, &[1, 2, 5], 0..=8, 0, 16), Some(vec![5, 5, 8]));
+        assert_eq!(goal_seek(&processor, &[1, 2], 0..=8, 0, -1), None);
+    }
+
+    #[test]
+    fn test_goal_seek_invalid_candidates() {
+        // 0 is not an op code, 1 adds memory[0] to itself
+        let processor = "1,0,0,0,99".parse::<Processor>().unwrap();
+        assert_eq!(try_with_patches(&processor, &[(0, 0)], 0, MAX_STEPS), Err(RunError::UnknownOpCode { address: 0, op_code: 0 }));
+        assert_eq!(try_with_patches(&processor, &[(3, -1)], 0, MAX_STEPS), Err(RunError::InvalidAddress { address: 0, target: -1 }));
+        assert_eq!(goal_seek(&processor, &[0], 0..=5, 0, 2), Some(vec![1]));
+        assert_eq!(goal_seek(&processor, &[3], -5..=0, 0, 2), Some(vec![0]));
+    }
+
+    #[test]
+    fn test_goal_seek_endless_candidates() {
+        // jumps back to the start forever when memory[1] is 0
+        let processor = "1106,0,0,1101,1,1,0,99".parse::<Processor>().unwrap();
+        assert_eq!(try_with_patches(&processor, &[], 0, 100), Err(RunError::StepLimit(100)));
+        assert_eq!(try_with_patches(&processor, &[(1, 3)], 0, 100), Ok(2));
+        assert_eq!(goal_seek(&processor, &[1], 0..=3, 0, 2), Some(vec![1]));
+        assert_eq!(goal_seek(&processor, &[1], 0..=3, 0, 3), None);
+    }
+}
@@ -0,0 +1,33 @@
+use std::env;
+
+use program_alarm::intcode_computer::Processor;
+use program_alarm::run_with_patches;
+use program_alarm::symbolic::{solve, Symbol};
+
+fn part1(processor: &Processor) {
+    println!("result: {}", run_with_patches(processor, &[(1, 12), (2, 2)], 0));
+}
+
+fn part2(processor: &Processor, target: i64) {
+    let (symbol, result) = solve(processor, target);
+    match symbol {
+        Symbol::Known(ref expression) if expression.is_linear() => println!("memory[0] = {}", expression),
+        Symbol::Known(expression) => println!("memory[0] = {}, not linear, searching", expression),
+        Symbol::Unknown => println!("memory[0] cannot be expressed from noun and verb, searching")
+    }
+    match result {
+        Some((noun, verb)) => println!("result: {}", noun*100 + verb),
+        None => println!("No noun and verb produce {}", target)
+    }
+}
+
+fn main() {
+    let args:Vec<String> = env::args().collect();
+    let target: i64 = match args.get(1) {
+        Some(arg) => arg.parse::<i64>().expect("Invalid target"),
+        None => 19690720
+    };
+    let processor = Processor::init("input.txt");
+    part1(&processor);
+    part2(&processor, target);
+}
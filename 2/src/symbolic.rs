@@ -0,0 +1,260 @@
+use std::collections::BTreeMap;
+use std::fmt;
+
+use crate::goal_seek;
+use crate::intcode_computer::Processor;
+
+pub const MAX_INIT: i64 = 99;
+
+// Polynomial in noun and verb: (noun degree, verb degree) -> coefficient
+#[derive(Clone, Debug, PartialEq)]
+pub struct Polynomial {
+    terms: BTreeMap<(u32, u32), i64>
+}
+
+impl Polynomial {
+    fn constant(value: i64) -> Polynomial {
+        Polynomial::monomial(0, 0, value)
+    }
+
+    fn monomial(noun_degree: u32, verb_degree: u32, coefficient: i64) -> Polynomial {
+        let mut terms = BTreeMap::new();
+        if coefficient != 0 {
+            terms.insert((noun_degree, verb_degree), coefficient);
+        }
+        Polynomial { terms }
+    }
+
+    fn as_constant(&self) -> Option<i64> {
+        match self.terms.len() {
+            0 => Some(0),
+            1 => self.terms.get(&(0, 0)).copied(),
+            _ => None
+        }
+    }
+
+    fn coefficient(&self, noun_degree: u32, verb_degree: u32) -> i64 {
+        self.terms.get(&(noun_degree, verb_degree)).copied().unwrap_or(0)
+    }
+
+    pub fn is_linear(&self) -> bool {
+        self.terms.keys().all(|(n, v)| n + v <= 1)
+    }
+
+    fn add(&self, other: &Polynomial) -> Option<Polynomial> {
+        let mut terms = self.terms.clone();
+        for (degrees, coefficient) in &other.terms {
+            let sum = terms.get(degrees).copied().unwrap_or(0).checked_add(*coefficient)?;
+            if sum == 0 {
+                terms.remove(degrees);
+            } else {
+                terms.insert(*degrees, sum);
+            }
+        }
+        Some(Polynomial { terms })
+    }
+
+    fn mul(&self, other: &Polynomial) -> Option<Polynomial> {
+        let mut ret = Polynomial::constant(0);
+        for ((n1, v1), c1) in &self.terms {
+            for ((n2, v2), c2) in &other.terms {
+                let product = Polynomial::monomial(n1 + n2, v1 + v2, c1.checked_mul(*c2)?);
+                ret = ret.add(&product)?;
+            }
+        }
+        Some(ret)
+    }
+}
+
+impl fmt::Display for Polynomial {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.terms.is_empty() {
+            return write!(f, "0");
+        }
+        let mut first = true;
+        for ((n, v), c) in self.terms.iter().rev() {
+            let mut factors = Vec::<String>::new();
+            if c.abs() != 1 || (*n == 0 && *v == 0) {
+                factors.push(c.abs().to_string());
+            }
+            for (name, degree) in [("noun", *n), ("verb", *v)].iter() {
+                match degree {
+                    0 => {},
+                    1 => factors.push(name.to_string()),
+                    _ => factors.push(format!("{}^{}", name, degree))
+                }
+            }
+            let sign = if *c < 0 { "-" } else { "+" };
+            if first {
+                if *c < 0 {
+                    write!(f, "-")?;
+                }
+                first = false;
+            } else {
+                write!(f, " {} ", sign)?;
+            }
+            write!(f, "{}", factors.join("*"))?;
+        }
+        Ok(())
+    }
+}
+
+// Value of a memory cell. Unknown is used when the value depends on noun or
+// verb through an address, which a polynomial cannot express.
+#[derive(Clone, Debug, PartialEq)]
+pub enum Symbol {
+    Known(Polynomial),
+    Unknown
+}
+
+// Unknown for programs too short to hold noun and verb.
+pub fn symbolic_result(program: &[i64]) -> Symbol {
+    if program.len() < 3 {
+        return Symbol::Unknown;
+    }
+    let mut memory: Vec<Symbol> = program.iter()
+        .map(|v| Symbol::Known(Polynomial::constant(*v)))
+        .collect();
+    memory[1] = Symbol::Known(Polynomial::monomial(1, 0, 1));
+    memory[2] = Symbol::Known(Polynomial::monomial(0, 1, 1));
+
+    let concrete = |symbol: &Symbol| -> Option<usize> {
+        match symbol {
+            Symbol::Known(p) => p.as_constant().map(|c| c as usize),
+            Symbol::Unknown => None
+        }
+    };
+
+    for cur_inst in 0..memory.len()/4 {
+        let cur_base_index = cur_inst*4;
+        let op_code = match concrete(&memory[cur_base_index]) {
+            Some(op_code) => op_code,
+            None => return Symbol::Unknown
+        };
+        if op_code == 99 {
+            break;
+        }
+        let operand = |offset: usize| -> Symbol {
+            match concrete(&memory[cur_base_index + offset]) {
+                Some(address) if address < memory.len() => memory[address].clone(),
+                _ => Symbol::Unknown
+            }
+        };
+        let res = match (op_code, operand(1), operand(2)) {
+            (1, Symbol::Known(op1), Symbol::Known(op2)) => op1.add(&op2),
+            (2, Symbol::Known(op1), Symbol::Known(op2)) => op1.mul(&op2),
+            (1, _, _) | (2, _, _) => None,
+            _ => return Symbol::Unknown
+        };
+        let res = match res {
+            Some(p) => Symbol::Known(p),
+            None => Symbol::Unknown
+        };
+        match concrete(&memory[cur_base_index + 3]) {
+            Some(res_index) if res_index < memory.len() => memory[res_index] = res,
+            _ => return Symbol::Unknown
+        }
+    }
+    memory[0].clone()
+}
+
+// Solves a*noun + b*verb + c = target over 0..=MAX_INIT and returns the
+// solution with the smallest 100*noun+verb.
+pub fn solve_linear(expression: &Polynomial, target: i64) -> Option<(i64, i64)> {
+    let a = expression.coefficient(1, 0);
+    let b = expression.coefficient(0, 1);
+    let c = expression.coefficient(0, 0);
+    for noun in 0..=MAX_INIT {
+        let rest = target - c - a*noun;
+        if b == 0 {
+            if rest == 0 {
+                return Some((noun, 0));
+            }
+        } else if rest % b == 0 && rest / b >= 0 && rest / b <= MAX_INIT {
+            return Some((noun, rest / b));
+        }
+    }
+    None
+}
+
+// Noun and verb leaving target in memory[0], with the smallest 100*noun+verb.
+// Linear programs are solved directly, others fall back to goal_seek. The
+// symbolic value of memory[0] tells which way was taken. Programs too short
+// to hold noun and verb have no solution.
+pub fn solve(processor: &Processor, target: i64) -> (Symbol, Option<(i64, i64)>) {
+    if processor.memory().len() < 3 {
+        return (Symbol::Unknown, None);
+    }
+    let symbol = symbolic_result(processor.memory());
+    let result = match &symbol {
+        Symbol::Known(expression) if expression.is_linear() => solve_linear(expression, target),
+        _ => goal_seek(processor, &[1, 2], 0..=MAX_INIT, 0, target).map(|values| (values[0], values[1]))
+    };
+    (symbol, result)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::run_with_patches;
+
+    fn processor(program: &[i64]) -> Processor {
+        let mut program = program.to_vec();
+        program.resize(104, 0);
+        let program: Vec<String> = program.iter().map(i64::to_string).collect();
+        program.join(",").parse::<Processor>().unwrap()
+    }
+
+    fn brute_force(processor: &Processor, target: i64) -> Option<(i64, i64)> {
+        (0..=MAX_INIT)
+            .flat_map(|noun| (0..=MAX_INIT).map(move |verb| (noun, verb)))
+            .find(|(noun, verb)| run_with_patches(processor, &[(1, *noun), (2, *verb)], 0) == target)
+    }
+
+    #[test]
+    fn test_linear() {
+        // memory[0] = 3*noun + verb + 7
+        let processor = processor(&[1, 0, 0, 3, 2, 1, 17, 3, 1, 3, 2, 3, 1, 3, 18, 0, 99, 3, 7]);
+        let (symbol, result) = solve(&processor, 152);
+        match symbol {
+            Symbol::Known(expression) => assert_eq!(expression.to_string(), "3*noun + verb + 7"),
+            Symbol::Unknown => panic!("memory[0] should be known")
+        }
+        assert_eq!(result, Some((16, 97)));
+        assert_eq!(result, brute_force(&processor, 152));
+    }
+
+    #[test]
+    fn test_non_linear() {
+        // memory[0] = noun*verb
+        let processor = processor(&[1, 0, 0, 3, 2, 1, 2, 0, 99]);
+        let (symbol, result) = solve(&processor, 391);
+        match symbol {
+            Symbol::Known(expression) => assert!(!expression.is_linear()),
+            Symbol::Unknown => panic!("memory[0] should be known")
+        }
+        assert_eq!(result, Some((17, 23)));
+        assert_eq!(result, brute_force(&processor, 391));
+    }
+
+    #[test]
+    fn test_no_solution() {
+        let linear = processor(&[1, 0, 0, 3, 2, 1, 17, 3, 1, 3, 2, 3, 1, 3, 18, 0, 99, 3, 7]);
+        assert_eq!(solve(&linear, 6), (symbolic_result(linear.memory()), None));
+        assert_eq!(solve(&linear, 3 * 99 + 99 + 8).1, None);
+        let non_linear = processor(&[1, 0, 0, 3, 2, 1, 2, 0, 99]);
+        assert_eq!(solve(&non_linear, 101).1, None);
+        assert_eq!(solve(&"99".parse::<Processor>().unwrap(), 99), (Symbol::Unknown, None));
+        assert_eq!(symbolic_result(&[]), Symbol::Unknown);
+    }
+
+    #[test]
+    fn test_unresolved_address() {
+        // memory[0] = memory[noun] + memory[verb]
+        let processor = processor(&[1, 0, 0, 0, 99, 42]);
+        let (symbol, result) = solve(&processor, 141);
+        assert_eq!(symbol, Symbol::Unknown);
+        assert_eq!(result, Some((4, 5)));
+        assert_eq!(result, brute_force(&processor, 141));
+    }
+}
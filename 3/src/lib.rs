@@ -0,0 +1,125 @@
+use std::fmt;
+
+pub mod svg;
+pub mod sweep;
+
+use sweep::Crossing;
+
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct Point {
+    pub x: i64,
+    pub y: i64
+}
+
+impl Point {
+    pub const ORIGIN: Point = Point { x: 0, y: 0 };
+
+    pub fn manhattan_distance(self) -> i64 {
+        self.x.abs() + self.y.abs()
+    }
+
+    pub fn distance_to(self, other: Point) -> i64 {
+        (self.x - other.x).abs() + (self.y - other.y).abs()
+    }
+}
+
+#[derive(Debug, PartialEq)]
+pub enum ParseError {
+    EmptyMove { position: usize },
+    InvalidDirection { position: usize, direction: char },
+    InvalidLength { position: usize, length: String }
+}
+
+impl fmt::Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ParseError::EmptyMove { position } => write!(f, "move {} is empty", position),
+            ParseError::InvalidDirection { position, direction } => write!(f, "move {}: invalid direction {:?}, expected R, L, U or D", position, direction),
+            ParseError::InvalidLength { position, length } => write!(f, "move {}: invalid length {:?}", position, length)
+        }
+    }
+}
+
+// Corners of the wire path, starting with the central port. Moves are
+// numbered from 1 in errors.
+pub fn parse_coords(line: &str) -> Result<Vec<Point>, ParseError> {
+    let mut cur = Point::ORIGIN;
+    let mut coords = vec![cur];
+    for (index, c) in line.trim().split(',').enumerate() {
+        let position = index + 1;
+        let c = c.trim();
+        let cur_op = c.chars().next().ok_or(ParseError::EmptyMove { position })?;
+        let length = &c[cur_op.len_utf8()..];
+        let cur_arg = length.parse::<i64>().ok().filter(|arg| *arg >= 0)
+            .ok_or_else(|| ParseError::InvalidLength { position, length: length.to_string() })?;
+        match cur_op {
+            'R' => cur.x += cur_arg,
+            'L' => cur.x -= cur_arg,
+            'U' => cur.y += cur_arg,
+            'D' => cur.y -= cur_arg,
+            _ => return Err(ParseError::InvalidDirection { position, direction: cur_op })
+        };
+        coords.push(cur);
+    }
+    Ok(coords)
+}
+
+#[derive(Debug, PartialEq)]
+pub struct ClosestCrossings<'a> {
+    pub by_distance: Option<&'a Crossing>,
+    pub by_steps: Option<&'a Crossing>
+}
+
+// Closest crossings of different wires to the central port, by Manhattan
+// distance and by combined steps. The central port itself, where all wires
+// cross, and wires crossing themselves do not count.
+pub fn closest_crossings(crossings: &[Crossing]) -> ClosestCrossings<'_> {
+    let mut closest = ClosestCrossings { by_distance: None, by_steps: None };
+    for crossing in crossings.iter().filter(|crossing| crossing.point != Point::ORIGIN && crossing.wires().len() > 1) {
+        let distance = crossing.point.manhattan_distance();
+        if closest.by_distance.map_or(true, |other| (distance, crossing.point) < (other.point.manhattan_distance(), other.point)) {
+            closest.by_distance = Some(crossing);
+        }
+        let steps = crossing.combined_steps();
+        if closest.by_steps.map_or(true, |other| (steps, crossing.point) < (other.combined_steps(), other.point)) {
+            closest.by_steps = Some(crossing);
+        }
+    }
+    closest
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::sweep::find_crossings;
+
+    fn answers(first: &str, second: &str) -> (i64, u64) {
+        let wires = vec![parse_coords(first).unwrap(), parse_coords(second).unwrap()];
+        let crossings = find_crossings(&wires);
+        let closest = closest_crossings(&crossings);
+        (closest.by_distance.unwrap().point.manhattan_distance(), closest.by_steps.unwrap().combined_steps())
+    }
+
+    #[test]
+    fn test_examples() {
+        assert_eq!(answers("R8,U5,L5,D3", "U7,R6,D4,L4"), (6, 30));
+        assert_eq!(answers("R75,D30,R83,U83,L12,D49,R71,U7,L72", "U62,R66,U55,R34,D71,R55,D58,R83"), (159, 610));
+        assert_eq!(answers("R98,U47,R26,D63,R33,U87,L62,D20,R33,U53,R51", "U98,R91,D20,R16,D67,R40,U7,R15,U6,R7"), (135, 410));
+    }
+
+    #[test]
+    fn test_no_crossing() {
+        let wires = vec![parse_coords("R8").unwrap(), parse_coords("L8").unwrap()];
+        let crossings = find_crossings(&wires);
+        assert_eq!(crossings.len(), 1);
+        assert_eq!(closest_crossings(&crossings), ClosestCrossings { by_distance: None, by_steps: None });
+    }
+
+    #[test]
+    fn test_parse_errors() {
+        assert_eq!(parse_coords("R8,X5"), Err(ParseError::InvalidDirection { position: 2, direction: 'X' }));
+        assert_eq!(parse_coords("R8,U5,Lfive"), Err(ParseError::InvalidLength { position: 3, length: "five".to_string() }));
+        assert_eq!(parse_coords("R8,U-5"), Err(ParseError::InvalidLength { position: 2, length: "-5".to_string() }));
+        assert_eq!(parse_coords("R8,,U5"), Err(ParseError::EmptyMove { position: 2 }));
+    }
+}
@@ -0,0 +1,60 @@
+use std::env;
+use std::fs;
+use std::process;
+
+use crossed_wires::svg::render_svg;
+use crossed_wires::sweep::find_crossings;
+use crossed_wires::{closest_crossings, parse_coords, Point};
+
+fn main() {
+    let args:Vec<String> = env::args().collect();
+    let mut input_filename = "input.txt".to_string();
+    let mut svg_filename: Option<String> = None;
+    let mut verbose = false;
+    let mut it = args.iter().skip(1);
+    while let Some(arg) = it.next() {
+        match arg.as_str() {
+            "--svg" => svg_filename = Some(it.next().expect("Missing SVG file name").clone()),
+            "--verbose" => verbose = true,
+            _ if !arg.starts_with("--") => input_filename = arg.clone(),
+            _ => panic!("Syntax : {} [--svg <file>] [--verbose] [input file]", args[0])
+        }
+    }
+    let content: String = fs::read_to_string(&input_filename)
+        .unwrap_or_else(|_| panic!("Something went wrong reading {}", input_filename));
+    let mut wires = Vec::<Vec<Point>>::new();
+    for (index, line) in content.lines().enumerate().filter(|(_, line)| !line.trim().is_empty()) {
+        match parse_coords(line) {
+            Ok(wire) => wires.push(wire),
+            Err(err) => {
+                eprintln!("{}:{}: {}", input_filename, index + 1, err);
+                process::exit(1);
+            }
+        }
+    }
+
+    let crossings = find_crossings(&wires);
+    if verbose {
+        for crossing in crossings.iter().filter(|crossing| crossing.point != Point::ORIGIN) {
+            let steps: Vec<String> = crossing.visits.iter()
+                .map(|visit| format!("wire {} after {} steps", visit.wire, visit.steps))
+                .collect();
+            let kind = if crossing.is_self_crossing() { " (self crossing)" } else { "" };
+            println!("{:?}{}: {}", crossing.point, kind, steps.join(", "));
+        }
+    }
+    let closest = closest_crossings(&crossings);
+    match (closest.by_distance, closest.by_steps) {
+        (Some(by_distance), Some(by_steps)) => {
+            println!("closest collision {:?}", by_distance.point);
+            println!("manhattan distance {}", by_distance.point.manhattan_distance());
+            println!("closest collision by wire length {:?}", by_steps.point);
+            println!("wire length {}", by_steps.combined_steps());
+        },
+        _ => println!("No collision found")
+    }
+    if let Some(svg_filename) = svg_filename {
+        fs::write(&svg_filename, render_svg(&wires, &crossings))
+            .unwrap_or_else(|_| panic!("Something went wrong writing {}", svg_filename));
+    }
+}
@@ -0,0 +1,91 @@
+use std::fmt::Write;
+
+use crate::sweep::Crossing;
+use crate::{closest_crossings, Point};
+
+const COLORS: [&str; 6] = ["#1f77b4", "#ff7f0e", "#2ca02c", "#9467bd", "#8c564b", "#e377c2"];
+const CROSSING_COLOR: &str = "#7f7f7f";
+const DISTANCE_WINNER_COLOR: &str = "#d62728";
+const STEPS_WINNER_COLOR: &str = "#17becf";
+
+// The y axis goes up for wires and down in SVG.
+fn svg_coords(point: Point) -> (i64, i64) {
+    (point.x, -point.y)
+}
+
+// Wires from the central port, one color each, every crossing labelled with its
+// Manhattan distance and combined steps. The closest crossing by distance and
+// by steps are highlighted.
+pub fn render_svg(wires: &[Vec<Point>], crossings: &[Crossing]) -> String {
+    let all_points = || wires.iter().flatten().copied().chain(std::iter::once(Point::ORIGIN));
+    let min_x = all_points().map(|p| p.x).min().unwrap();
+    let max_x = all_points().map(|p| p.x).max().unwrap();
+    let min_y = all_points().map(|p| -p.y).min().unwrap();
+    let max_y = all_points().map(|p| -p.y).max().unwrap();
+    let size = (max_x - min_x).max(max_y - min_y).max(1) as f64;
+    let margin = (size * 0.05).max(1.);
+    let stroke = (size / 1000.).max(0.05);
+    let radius = stroke * 3.;
+    let font_size = stroke * 10.;
+
+    let mut svg = String::new();
+    writeln!(svg, "<svg xmlns=\"http://www.w3.org/2000/svg\" viewBox=\"{} {} {} {}\">",
+             min_x as f64 - margin, min_y as f64 - margin,
+             (max_x - min_x) as f64 + 2. * margin, (max_y - min_y) as f64 + 2. * margin).unwrap();
+    writeln!(svg, "<rect x=\"{}\" y=\"{}\" width=\"{}\" height=\"{}\" fill=\"white\"/>",
+             min_x as f64 - margin, min_y as f64 - margin,
+             (max_x - min_x) as f64 + 2. * margin, (max_y - min_y) as f64 + 2. * margin).unwrap();
+    for (wire, points) in wires.iter().enumerate() {
+        let path: Vec<String> = points.iter().map(|point| {
+            let (x, y) = svg_coords(*point);
+            format!("{},{}", x, y)
+        }).collect();
+        writeln!(svg, "<polyline points=\"{}\" fill=\"none\" stroke=\"{}\" stroke-width=\"{}\"><title>wire {}</title></polyline>",
+                 path.join(" "), COLORS[wire % COLORS.len()], stroke, wire).unwrap();
+    }
+    writeln!(svg, "<circle cx=\"0\" cy=\"0\" r=\"{}\" fill=\"black\"><title>central port</title></circle>", radius * 1.5).unwrap();
+
+    let closest = closest_crossings(crossings);
+    for crossing in crossings.iter().filter(|crossing| crossing.point != Point::ORIGIN) {
+        let (x, y) = svg_coords(crossing.point);
+        let mut winner_labels = Vec::<&str>::new();
+        let mut color = CROSSING_COLOR;
+        if closest.by_steps == Some(crossing) {
+            winner_labels.push("fewest steps");
+            color = STEPS_WINNER_COLOR;
+        }
+        if closest.by_distance == Some(crossing) {
+            winner_labels.push("closest");
+            color = DISTANCE_WINNER_COLOR;
+        }
+        let (r, weight) = if winner_labels.is_empty() { (radius, "normal") } else { (radius * 2., "bold") };
+        let mut label = format!("d={} s={}", crossing.point.manhattan_distance(), crossing.combined_steps());
+        if !winner_labels.is_empty() {
+            label = format!("{} ({})", label, winner_labels.join(", "));
+        }
+        writeln!(svg, "<circle cx=\"{}\" cy=\"{}\" r=\"{}\" fill=\"{}\"><title>{:?}</title></circle>",
+                 x, y, r, color, crossing.point).unwrap();
+        writeln!(svg, "<text x=\"{}\" y=\"{}\" font-size=\"{}\" font-weight=\"{}\" fill=\"{}\">{}</text>",
+                 x as f64 + r, y as f64 - r, font_size, weight, color, label).unwrap();
+    }
+    svg += "</svg>\n";
+    svg
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::parse_coords;
+    use crate::sweep::find_crossings;
+
+    #[test]
+    fn test_render_svg() {
+        let wires = vec![parse_coords("R8,U5,L5,D3").unwrap(), parse_coords("U7,R6,D4,L4").unwrap()];
+        let svg = render_svg(&wires, &find_crossings(&wires));
+        assert!(svg.starts_with("<svg xmlns=\"http://www.w3.org/2000/svg\" viewBox=\"-1 -8 10 9\">"));
+        assert_eq!(svg.matches("<polyline").count(), 2);
+        assert!(svg.contains("points=\"0,0 8,0 8,-5 3,-5 3,-2\""));
+        assert!(svg.contains(">d=6 s=40 (closest)</text>"));
+        assert!(svg.contains(">d=11 s=30 (fewest steps)</text>"));
+    }
+}
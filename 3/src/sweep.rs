@@ -0,0 +1,254 @@
+use std::collections::{BTreeMap, HashMap};
+
+use crate::Point;
+
+// One pass of a wire on a crossing point.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, PartialOrd, Ord)]
+pub struct Visit {
+    pub wire: usize,
+    pub steps: u64
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct Crossing {
+    pub point: Point,
+    // sorted by wire, then steps
+    pub visits: Vec<Visit>
+}
+
+impl Crossing {
+    // Fewest steps for wire to reach the point.
+    pub fn steps(&self, wire: usize) -> Option<u64> {
+        self.visits.iter().find(|visit| visit.wire == wire).map(|visit| visit.steps)
+    }
+
+    pub fn wires(&self) -> Vec<usize> {
+        let mut wires: Vec<usize> = self.visits.iter().map(|visit| visit.wire).collect();
+        wires.dedup();
+        wires
+    }
+
+    // Sum over the wires of the fewest steps to reach the point.
+    pub fn combined_steps(&self) -> u64 {
+        self.wires().iter().map(|wire| self.steps(*wire).unwrap()).sum()
+    }
+
+    pub fn is_self_crossing(&self) -> bool {
+        self.wires().len() < self.visits.len()
+    }
+}
+
+// Points covered by a wire segment, excluding its first point (the last point
+// of the previous segment) except for the first segment of the wire, so that
+// each point of a wire belongs to one segment per pass.
+#[derive(Copy, Clone, Debug)]
+struct Segment {
+    wire: usize,
+    horizontal: bool,
+    // fixed coordinate, y for an horizontal segment
+    level: i64,
+    // covered range of the other coordinate
+    low: i64,
+    high: i64,
+    from: Point,
+    steps: u64
+}
+
+impl Segment {
+    fn point(&self, position: i64) -> Point {
+        if self.horizontal {
+            Point { x: position, y: self.level }
+        } else {
+            Point { x: self.level, y: position }
+        }
+    }
+
+    fn visit(&self, point: Point) -> Visit {
+        Visit {
+            wire: self.wire,
+            steps: self.steps + self.from.distance_to(point) as u64
+        }
+    }
+}
+
+fn segments(wires: &[Vec<Point>]) -> Vec<Segment> {
+    let mut segments = Vec::<Segment>::new();
+    for (wire, points) in wires.iter().enumerate() {
+        let mut steps = 0;
+        for (index, pair) in points.windows(2).enumerate() {
+            let (start, end) = (pair[0], pair[1]);
+            let horizontal = start.y == end.y;
+            let (level, from, to) = if horizontal { (start.y, start.x, end.x) } else { (start.x, start.y, end.y) };
+            let skip = if index == 0 { 0 } else { (to - from).signum() };
+            let (low, high) = if from <= to { (from + skip, to) } else { (to, from + skip) };
+            if from != to || index == 0 {
+                segments.push(Segment { wire, horizontal, level, low, high, from: start, steps });
+            }
+            steps += start.distance_to(end) as u64;
+        }
+    }
+    segments
+}
+
+// Overlapping parts of segments on the same line, sweeping each line from its
+// lowest position.
+fn collinear_overlaps(segments: &[Segment], horizontal: bool, found: &mut dyn FnMut(usize, usize, Point)) {
+    let mut lines = BTreeMap::<i64, Vec<usize>>::new();
+    for (id, segment) in segments.iter().enumerate().filter(|(_, segment)| segment.horizontal == horizontal) {
+        lines.entry(segment.level).or_default().push(id);
+    }
+    for ids in lines.values_mut() {
+        ids.sort_by_key(|id| segments[*id].low);
+        let mut active = Vec::<usize>::new();
+        for id in ids.iter() {
+            let segment = &segments[*id];
+            active.retain(|other| segments[*other].high >= segment.low);
+            for other in &active {
+                for position in segment.low..=segment.high.min(segments[*other].high) {
+                    found(*other, *id, segment.point(position));
+                }
+            }
+            active.push(*id);
+        }
+    }
+}
+
+#[derive(Copy, Clone, PartialEq, Eq, PartialOrd, Ord)]
+enum Event {
+    Insert,
+    Query,
+    Remove
+}
+
+// Vertical segments crossing horizontal ones, sweeping from left to right with
+// the horizontal segments under the sweep line indexed by y.
+fn perpendicular_crossings(segments: &[Segment], found: &mut dyn FnMut(usize, usize, Point)) {
+    let mut events = Vec::<(i64, Event, usize)>::new();
+    for (id, segment) in segments.iter().enumerate() {
+        if segment.horizontal {
+            events.push((segment.low, Event::Insert, id));
+            events.push((segment.high, Event::Remove, id));
+        } else {
+            events.push((segment.level, Event::Query, id));
+        }
+    }
+    events.sort_unstable();
+    let mut active = BTreeMap::<i64, Vec<usize>>::new();
+    for (x, event, id) in events {
+        let segment = &segments[id];
+        match event {
+            Event::Insert => active.entry(segment.level).or_default().push(id),
+            Event::Remove => {
+                let ids = active.get_mut(&segment.level).unwrap();
+                ids.retain(|other| *other != id);
+                if ids.is_empty() {
+                    active.remove(&segment.level);
+                }
+            },
+            Event::Query => {
+                for (y, ids) in active.range(segment.low..=segment.high) {
+                    for other in ids {
+                        found(*other, id, Point { x, y: *y });
+                    }
+                }
+            }
+        }
+    }
+}
+
+// Every point where at least two passes of wires meet, the central port
+// included, with the steps each pass took to get there. A wire crossing its own
+// path is reported as well.
+pub fn find_crossings(wires: &[Vec<Point>]) -> Vec<Crossing> {
+    let segments = segments(wires);
+    let mut points = HashMap::<Point, BTreeMap<usize, Visit>>::new();
+    let mut found = |first: usize, second: usize, point: Point| {
+        let visits = points.entry(point).or_default();
+        visits.insert(first, segments[first].visit(point));
+        visits.insert(second, segments[second].visit(point));
+    };
+    collinear_overlaps(&segments, true, &mut found);
+    collinear_overlaps(&segments, false, &mut found);
+    perpendicular_crossings(&segments, &mut found);
+
+    let mut crossings: Vec<Crossing> = points.into_iter().map(|(point, visits)| {
+        let mut visits: Vec<Visit> = visits.values().copied().collect();
+        visits.sort_unstable();
+        Crossing { point, visits }
+    }).collect();
+    crossings.sort_by_key(|crossing| crossing.point);
+    crossings
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::parse_coords;
+
+    // Every point of every wire, one by one.
+    fn brute_force(wires: &[Vec<Point>]) -> Vec<Crossing> {
+        let mut points = HashMap::<Point, Vec<Visit>>::new();
+        for (wire, corners) in wires.iter().enumerate() {
+            let mut steps = 0;
+            points.entry(corners[0]).or_default().push(Visit { wire, steps });
+            for pair in corners.windows(2) {
+                let mut cur = pair[0];
+                while cur != pair[1] {
+                    cur.x += (pair[1].x - cur.x).signum();
+                    cur.y += (pair[1].y - cur.y).signum();
+                    steps += 1;
+                    points.entry(cur).or_default().push(Visit { wire, steps });
+                }
+            }
+        }
+        let mut crossings: Vec<Crossing> = points.into_iter().filter(|(_, visits)| visits.len() > 1)
+            .map(|(point, mut visits)| {
+                visits.sort_unstable();
+                Crossing { point, visits }
+            }).collect();
+        crossings.sort_by_key(|crossing| crossing.point);
+        crossings
+    }
+
+    #[test]
+    fn test_perpendicular() {
+        let wires = vec![parse_coords("R8,U5,L5,D3").unwrap(), parse_coords("U7,R6,D4,L4").unwrap()];
+        let crossings: Vec<Crossing> = find_crossings(&wires).into_iter().filter(|c| c.point != Point::ORIGIN).collect();
+        assert_eq!(crossings, vec![
+            Crossing { point: Point { x: 3, y: 3 }, visits: vec![Visit { wire: 0, steps: 20 }, Visit { wire: 1, steps: 20 }] },
+            Crossing { point: Point { x: 6, y: 5 }, visits: vec![Visit { wire: 0, steps: 15 }, Visit { wire: 1, steps: 15 }] }
+        ]);
+        assert_eq!(crossings[0].combined_steps(), 40);
+    }
+
+    #[test]
+    fn test_collinear_and_self_crossing() {
+        let wires = vec![parse_coords("R5,U2,L2,D4").unwrap(), parse_coords("D1,R2,U1,R6").unwrap()];
+        let crossings = find_crossings(&wires);
+        let points: Vec<Point> = crossings.iter().map(|c| c.point).collect();
+        assert!(points.contains(&Point { x: 4, y: 0 }));
+        let self_crossing = crossings.iter().find(|c| c.point == Point { x: 3, y: 0 }).unwrap();
+        assert!(self_crossing.is_self_crossing());
+        assert_eq!(self_crossing.steps(0), Some(3));
+        assert_eq!(self_crossing.steps(1), Some(5));
+        assert_eq!(crossings, brute_force(&wires));
+    }
+
+    #[test]
+    fn test_against_brute_force() {
+        let mut seed: u64 = 42;
+        let mut random = |modulo: u64| {
+            seed = seed.wrapping_mul(6364136223846793005).wrapping_add(1442695040888963407);
+            (seed >> 33) % modulo
+        };
+        for _ in 0..200 {
+            let wires: Vec<Vec<Point>> = (0..1 + random(3)).map(|_| {
+                let moves: Vec<String> = (0..1 + random(12))
+                    .map(|_| format!("{}{}", ['R', 'L', 'U', 'D'][random(4) as usize], random(6)))
+                    .collect();
+                parse_coords(&moves.join(",")).unwrap()
+            }).collect();
+            assert_eq!(find_crossings(&wires), brute_force(&wires), "wires {:?}", wires);
+        }
+    }
+}
@@ -0,0 +1,226 @@
+use std::ops::RangeInclusive;
+
+pub mod rules;
+
+// Passwords have non-decreasing digits and two adjacent equal digits. With
+// ExactPair, the two equal digits must not be part of a longer run.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum PairRule {
+    SomePair,
+    ExactPair
+}
+
+impl PairRule {
+    fn qualifies(self, run: u8) -> bool {
+        match self {
+            PairRule::SomePair => run >= 2,
+            PairRule::ExactPair => run == 2
+        }
+    }
+}
+
+pub(crate) fn digits(n: u128) -> Vec<u8> {
+    n.to_string().bytes().map(|b| b - b'0').collect()
+}
+
+fn from_digits(digits: &[u8]) -> Option<u128> {
+    digits.iter().try_fold(0u128, |n, digit| n.checked_mul(10)?.checked_add(u128::from(*digit)))
+}
+
+pub fn is_valid(n: u128, rule: PairRule) -> bool {
+    let digits = digits(n);
+    if digits.windows(2).any(|pair| pair[0] > pair[1]) {
+        return false;
+    }
+    let mut run = 1;
+    for (index, digit) in digits.iter().enumerate() {
+        if index + 1 < digits.len() && digits[index + 1] == *digit {
+            run += 1;
+        } else {
+            if rule.qualifies(run) {
+                return true;
+            }
+            run = 1;
+        }
+    }
+    false
+}
+
+// Digits of a non-decreasing number, as seen from left to right: last digit,
+// length of its run (3 standing for 3 or more) and whether an earlier run
+// already satisfied the rule.
+#[derive(Clone, Copy)]
+struct State {
+    last: u8,
+    run: u8,
+    satisfied: bool
+}
+
+impl State {
+    fn first(digit: u8) -> State {
+        State { last: digit, run: 1, satisfied: false }
+    }
+
+    fn push(self, digit: u8, rule: PairRule) -> State {
+        if digit == self.last {
+            State { run: (self.run + 1).min(3), ..self }
+        } else {
+            State { last: digit, run: 1, satisfied: self.satisfied || rule.qualifies(self.run) }
+        }
+    }
+
+    fn accepted(self, rule: PairRule) -> bool {
+        self.satisfied || rule.qualifies(self.run)
+    }
+}
+
+// Number of ways to complete a number with remaining digits from a state,
+// memoized by remaining, last digit, run and satisfied.
+struct Completions {
+    rule: PairRule,
+    memo: Vec<Option<u128>>
+}
+
+impl Completions {
+    fn new(rule: PairRule) -> Completions {
+        Completions { rule, memo: vec![None; 40 * 10 * 4 * 2] }
+    }
+
+    fn count(&mut self, remaining: usize, state: State) -> u128 {
+        if remaining == 0 {
+            return if state.accepted(self.rule) { 1 } else { 0 };
+        }
+        let key = ((remaining * 10 + state.last as usize) * 4 + state.run as usize) * 2 + state.satisfied as usize;
+        if let Some(count) = self.memo[key] {
+            return count;
+        }
+        let count = (state.last..=9).map(|digit| self.count(remaining - 1, state.push(digit, self.rule))).sum();
+        self.memo[key] = Some(count);
+        count
+    }
+
+    // Valid passwords in 1..=n: every shorter number, then the numbers as long
+    // as n, digit by digit while they share a prefix with n.
+    fn count_up_to(&mut self, n: u128) -> u128 {
+        if n == 0 {
+            return 0;
+        }
+        let digits = digits(n);
+        let mut total: u128 = (1..digits.len())
+            .map(|length| (1..=9).map(|first| self.count(length - 1, State::first(first))).sum::<u128>())
+            .sum();
+        let mut state: Option<State> = None;
+        for (index, digit) in digits.iter().enumerate() {
+            let lowest = state.map_or(1, |state| state.last);
+            let remaining = digits.len() - index - 1;
+            for smaller in lowest..*digit {
+                let next = state.map_or(State::first(smaller), |state| state.push(smaller, self.rule));
+                total += self.count(remaining, next);
+            }
+            if *digit < lowest {
+                return total;
+            }
+            state = Some(state.map_or(State::first(*digit), |state| state.push(*digit, self.rule)));
+        }
+        if state.unwrap().accepted(self.rule) {
+            total += 1;
+        }
+        total
+    }
+}
+
+// Number of valid passwords in range, in time proportional to the number of
+// digits.
+pub fn count(range: RangeInclusive<u128>, rule: PairRule) -> u128 {
+    if range.is_empty() {
+        return 0;
+    }
+    let mut completions = Completions::new(rule);
+    let below = match range.start().checked_sub(1) {
+        Some(before) => completions.count_up_to(before),
+        None => 0
+    };
+    completions.count_up_to(*range.end()) - below
+}
+
+// Smallest number with non-decreasing digits not lower than n.
+pub(crate) fn next_non_decreasing(n: u128) -> Option<u128> {
+    let mut digits = digits(n);
+    if let Some(index) = (1..digits.len()).find(|index| digits[*index] < digits[index - 1]) {
+        let previous = digits[index - 1];
+        for digit in digits[index..].iter_mut() {
+            *digit = previous;
+        }
+    }
+    from_digits(&digits)
+}
+
+// Valid passwords in increasing order, jumping over numbers with decreasing
+// digits.
+pub struct Matches {
+    next: Option<u128>,
+    end: u128,
+    rule: PairRule
+}
+
+impl Iterator for Matches {
+    type Item = u128;
+
+    fn next(&mut self) -> Option<u128> {
+        while let Some(candidate) = self.next.and_then(next_non_decreasing) {
+            if candidate > self.end {
+                break;
+            }
+            self.next = candidate.checked_add(1);
+            if is_valid(candidate, self.rule) {
+                return Some(candidate);
+            }
+        }
+        self.next = None;
+        None
+    }
+}
+
+pub fn matches(range: RangeInclusive<u128>, rule: PairRule) -> Matches {
+    Matches {
+        next: if range.is_empty() { None } else { Some(*range.start()) },
+        end: *range.end(),
+        rule
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_is_valid() {
+        assert!(is_valid(111111, PairRule::SomePair));
+        assert!(!is_valid(223450, PairRule::SomePair));
+        assert!(!is_valid(123789, PairRule::SomePair));
+        assert!(is_valid(112233, PairRule::ExactPair));
+        assert!(!is_valid(123444, PairRule::ExactPair));
+        assert!(is_valid(111122, PairRule::ExactPair));
+    }
+
+    #[test]
+    fn test_against_brute_force() {
+        for rule in [PairRule::SomePair, PairRule::ExactPair].iter() {
+            for range in [0..=0, 0..=200_000, 11..=11, 99..=1_000, 123_456..=124_000, 248_345..=346_315].iter() {
+                let expected: Vec<u128> = range.clone().filter(|n| is_valid(*n, *rule)).collect();
+                assert_eq!(count(range.clone(), *rule), expected.len() as u128, "{:?} {:?}", range, rule);
+                assert_eq!(matches(range.clone(), *rule).collect::<Vec<u128>>(), expected, "{:?} {:?}", range, rule);
+            }
+        }
+    }
+
+    #[test]
+    fn test_huge_ranges() {
+        // every 30 digit non-decreasing number has a pair, C(38, 8) of them
+        assert_eq!(count(10u128.pow(29)..=10u128.pow(30) - 1, PairRule::SomePair), 48_903_492);
+        assert_eq!(count(0..=u128::MAX, PairRule::ExactPair), count(0..=34 * 10u128.pow(37), PairRule::ExactPair));
+        assert_eq!(matches(u128::MAX - 10..=u128::MAX, PairRule::SomePair).count(), 0);
+        assert_eq!(matches(10u128.pow(37)..=u128::MAX, PairRule::SomePair).next(), Some(11_111_111_111_111_111_111_111_111_111_111_111_111));
+        assert_eq!(count(RangeInclusive::new(5, 4), PairRule::SomePair), 0);
+    }
+}
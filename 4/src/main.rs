@@ -0,0 +1,88 @@
+use std::env;
+use std::fs;
+
+use secure_container::rules::RuleSet;
+use secure_container::{count, matches, PairRule};
+
+fn pair_rule_set(rule: PairRule) -> RuleSet {
+    match rule {
+        PairRule::SomePair => RuleSet::parse(&["non-decreasing", "pair"]).unwrap(),
+        PairRule::ExactPair => RuleSet::parse(&["non-decreasing", "exact-pair"]).unwrap()
+    }
+}
+
+fn explain(rule_set: &RuleSet, candidate: u128) {
+    match rule_set.explain(candidate) {
+        Some((rule, reason)) => println!("{}: rejected by {}: {}", candidate, rule, reason),
+        None => println!("{}: accepted by {}", candidate, rule_set.names().join(", "))
+    }
+}
+
+fn main() {
+    let args:Vec<String> = env::args().collect();
+    let mut rules = vec![PairRule::SomePair, PairRule::ExactPair];
+    let mut rule_set: Option<RuleSet> = None;
+    let mut list = false;
+    let mut explained: Option<u128> = None;
+    let mut bounds = Vec::<u128>::new();
+    let mut it = args.iter().skip(1);
+    while let Some(arg) = it.next() {
+        let mut value = || it.next().unwrap_or_else(|| panic!("Missing value for {}", arg)).as_str();
+        match arg.as_str() {
+            "--rule" => {
+                rules = match value() {
+                    "some" => vec![PairRule::SomePair],
+                    "exact" => vec![PairRule::ExactPair],
+                    _ => panic!("Rule must be some or exact")
+                }
+            },
+            "--rules" => {
+                let specs: Vec<&str> = value().split(',').collect();
+                rule_set = Some(RuleSet::parse(&specs).unwrap_or_else(|err| panic!("{}", err)));
+            },
+            "--config" => {
+                let filename = value();
+                let content = fs::read_to_string(filename).unwrap_or_else(|_| panic!("Error reading {}", filename));
+                rule_set = Some(RuleSet::from_toml(&content).unwrap_or_else(|err| panic!("{}: {}", filename, err)));
+            },
+            "--explain" => explained = Some(value().parse::<u128>().expect("Invalid candidate")),
+            "--list" => list = true,
+            _ if !arg.starts_with("--") => bounds.push(arg.parse::<u128>().expect("Invalid bound of interval")),
+            _ => panic!("Unknown option {}", arg)
+        }
+    }
+    if let Some(candidate) = explained {
+        match &rule_set {
+            Some(rule_set) => explain(rule_set, candidate),
+            None => rules.iter().for_each(|rule| explain(&pair_rule_set(*rule), candidate))
+        }
+        return;
+    }
+    if bounds.len() != 2 {
+        panic!("Syntax : {} [--rule some|exact] [--rules <rule>,...] [--config <toml file>] [--list] <interval_start> <interval_end>\n\
+                \x20        {} [--rule some|exact] [--rules <rule>,...] [--config <toml file>] --explain <candidate>", args[0], args[0]);
+    }
+
+    match rule_set {
+        Some(rule_set) => {
+            let mut nb_valid = 0;
+            for password in rule_set.matches(bounds[0]..=bounds[1]) {
+                nb_valid += 1;
+                if list {
+                    println!("{}", password);
+                }
+            }
+            println!("nb valid: {}", nb_valid);
+        },
+        None => {
+            for rule in rules {
+                println!("{:?} nb valid: {}", rule, count(bounds[0]..=bounds[1], rule));
+                if list {
+                    for password in matches(bounds[0]..=bounds[1], rule) {
+                        println!("{}", password);
+                    }
+                }
+            }
+        }
+    }
+}
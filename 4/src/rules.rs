@@ -0,0 +1,325 @@
+use std::fmt;
+use std::ops::RangeInclusive;
+
+use crate::next_non_decreasing;
+
+#[derive(Debug, PartialEq)]
+pub enum RuleError {
+    UnknownRule(String),
+    InvalidBounds(String),
+    Config(String)
+}
+
+impl fmt::Display for RuleError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            RuleError::UnknownRule(spec) => write!(f, "unknown rule {:?}", spec),
+            RuleError::InvalidBounds(spec) => write!(f, "invalid bounds {:?}, expected <n>, <min>-<max> or <min>+", spec),
+            RuleError::Config(message) => write!(f, "invalid configuration: {}", message)
+        }
+    }
+}
+
+pub trait Rule {
+    // Specification the rule was parsed from.
+    fn name(&self) -> String;
+
+    // None when the digits satisfy the rule, otherwise why they do not.
+    fn rejection(&self, digits: &[u8]) -> Option<String>;
+
+    fn accepts(&self, digits: &[u8]) -> bool {
+        self.rejection(digits).is_none()
+    }
+}
+
+// Lengths of the runs of equal adjacent digits.
+fn runs(digits: &[u8]) -> Vec<(u8, usize)> {
+    let mut runs = Vec::<(u8, usize)>::new();
+    for digit in digits {
+        match runs.last_mut() {
+            Some((last, length)) if last == digit => *length += 1,
+            _ => runs.push((*digit, 1))
+        }
+    }
+    runs
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+struct Bounds {
+    min: usize,
+    max: Option<usize>
+}
+
+impl Bounds {
+    // n, min-max or min+
+    fn parse(spec: &str) -> Result<Bounds, RuleError> {
+        let invalid = || RuleError::InvalidBounds(spec.to_string());
+        let number = |s: &str| s.parse::<usize>().map_err(|_| invalid());
+        let bounds = if let Some(min) = spec.strip_suffix('+') {
+            Bounds { min: number(min)?, max: None }
+        } else if let Some((min, max)) = spec.split_once('-') {
+            Bounds { min: number(min)?, max: Some(number(max)?) }
+        } else {
+            Bounds { min: number(spec)?, max: Some(number(spec)?) }
+        };
+        if bounds.max.map_or(false, |max| max < bounds.min) {
+            return Err(invalid());
+        }
+        Ok(bounds)
+    }
+
+    fn contains(self, n: usize) -> bool {
+        n >= self.min && self.max.map_or(true, |max| n <= max)
+    }
+}
+
+impl fmt::Display for Bounds {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.max {
+            None => write!(f, "at least {}", self.min),
+            Some(max) if max == self.min => write!(f, "exactly {}", max),
+            Some(max) => write!(f, "{} to {}", self.min, max)
+        }
+    }
+}
+
+struct NonDecreasing;
+
+impl Rule for NonDecreasing {
+    fn name(&self) -> String {
+        "non-decreasing".to_string()
+    }
+
+    fn rejection(&self, digits: &[u8]) -> Option<String> {
+        let index = (1..digits.len()).find(|index| digits[*index] < digits[index - 1])?;
+        Some(format!("digit {} at position {} is lower than {}", digits[index], index + 1, digits[index - 1]))
+    }
+}
+
+struct Length {
+    spec: String,
+    bounds: Bounds
+}
+
+impl Rule for Length {
+    fn name(&self) -> String {
+        self.spec.clone()
+    }
+
+    fn rejection(&self, digits: &[u8]) -> Option<String> {
+        if self.bounds.contains(digits.len()) {
+            None
+        } else {
+            Some(format!("{} digits, expected {}", digits.len(), self.bounds))
+        }
+    }
+}
+
+// Some run of equal digits, of a given digit if any, with a length within
+// bounds.
+struct Run {
+    spec: String,
+    digit: Option<u8>,
+    bounds: Bounds
+}
+
+impl Rule for Run {
+    fn name(&self) -> String {
+        self.spec.clone()
+    }
+
+    fn rejection(&self, digits: &[u8]) -> Option<String> {
+        let runs = runs(digits);
+        if runs.iter().any(|(digit, length)| self.digit.map_or(true, |d| d == *digit) && self.bounds.contains(*length)) {
+            return None;
+        }
+        let lengths: Vec<String> = runs.iter().map(|(digit, length)| format!("{}x{}", length, digit)).collect();
+        let of = self.digit.map_or("equal digits".to_string(), |digit| format!("{}", digit));
+        Some(format!("no run of {} with a length of {} (runs: {})", of, self.bounds, lengths.join(", ")))
+    }
+}
+
+struct Not(Box<dyn Rule>);
+
+impl Rule for Not {
+    fn name(&self) -> String {
+        format!("!{}", self.0.name())
+    }
+
+    fn rejection(&self, digits: &[u8]) -> Option<String> {
+        match self.0.rejection(digits) {
+            Some(_) => None,
+            None => Some(format!("{} is satisfied", self.0.name()))
+        }
+    }
+}
+
+struct Any(Vec<Box<dyn Rule>>);
+
+impl Rule for Any {
+    fn name(&self) -> String {
+        self.0.iter().map(|rule| rule.name()).collect::<Vec<String>>().join("|")
+    }
+
+    fn rejection(&self, digits: &[u8]) -> Option<String> {
+        let mut reasons = Vec::<String>::new();
+        for rule in &self.0 {
+            reasons.push(format!("{}: {}", rule.name(), rule.rejection(digits)?));
+        }
+        Some(reasons.join(" and "))
+    }
+}
+
+// Rule specifications:
+//   non-decreasing     digits never decrease from left to right
+//   pair               two adjacent equal digits, same as run=2+
+//   exact-pair         two adjacent equal digits not part of a larger group,
+//                      same as run=2
+//   length=<bounds>    number of digits
+//   run=<bounds>       some run of equal digits with a length within bounds
+//   run[<d>]=<bounds>  same, for a run of digit d
+//   !<rule>            rule must not be satisfied
+//   <rule>|<rule>      one of the rules must be satisfied
+// where bounds are <n>, <min>-<max> or <min>+.
+pub fn parse_rule(spec: &str) -> Result<Box<dyn Rule>, RuleError> {
+    let spec = spec.trim();
+    if spec.contains('|') {
+        return Ok(Box::new(Any(spec.split('|').map(parse_rule).collect::<Result<Vec<Box<dyn Rule>>, RuleError>>()?)));
+    }
+    if let Some(negated) = spec.strip_prefix('!') {
+        return Ok(Box::new(Not(parse_rule(negated)?)));
+    }
+    let run = |digit: Option<u8>, bounds: &str| -> Result<Box<dyn Rule>, RuleError> {
+        Ok(Box::new(Run { spec: spec.to_string(), digit, bounds: Bounds::parse(bounds)? }))
+    };
+    match spec.split_once('=') {
+        None if spec == "non-decreasing" => Ok(Box::new(NonDecreasing)),
+        None if spec == "pair" => run(None, "2+"),
+        None if spec == "exact-pair" => run(None, "2"),
+        Some(("length", bounds)) => Ok(Box::new(Length { spec: spec.to_string(), bounds: Bounds::parse(bounds)? })),
+        Some(("run", bounds)) => run(None, bounds),
+        Some((name, bounds)) if name.len() == 6 && name.starts_with("run[") && name.ends_with(']') => {
+            match name.as_bytes()[4] {
+                digit @ b'0'..=b'9' => run(Some(digit - b'0'), bounds),
+                _ => Err(RuleError::UnknownRule(spec.to_string()))
+            }
+        },
+        _ => Err(RuleError::UnknownRule(spec.to_string()))
+    }
+}
+
+// Rules that must all be satisfied.
+pub struct RuleSet {
+    rules: Vec<Box<dyn Rule>>
+}
+
+impl RuleSet {
+    pub fn parse<S: AsRef<str>>(specs: &[S]) -> Result<RuleSet, RuleError> {
+        Ok(RuleSet {
+            rules: specs.iter().map(|spec| parse_rule(spec.as_ref())).collect::<Result<Vec<Box<dyn Rule>>, RuleError>>()?
+        })
+    }
+
+    // TOML configuration with the rule specifications in a rules array:
+    //   rules = ["non-decreasing", "exact-pair", "length=6"]
+    pub fn from_toml(content: &str) -> Result<RuleSet, RuleError> {
+        let config = content.parse::<toml::Value>().map_err(|err| RuleError::Config(err.to_string()))?;
+        let specs = config.get("rules").and_then(toml::Value::as_array)
+            .ok_or_else(|| RuleError::Config("missing rules array".to_string()))?
+            .iter().map(|spec| spec.as_str().ok_or_else(|| RuleError::Config(format!("rule {} is not a string", spec))))
+            .collect::<Result<Vec<&str>, RuleError>>()?;
+        RuleSet::parse(&specs)
+    }
+
+    // The first rule rejecting the candidate, with the reason.
+    pub fn explain(&self, candidate: u128) -> Option<(String, String)> {
+        let digits = crate::digits(candidate);
+        self.rules.iter().find_map(|rule| rule.rejection(&digits).map(|reason| (rule.name(), reason)))
+    }
+
+    pub fn names(&self) -> Vec<String> {
+        self.rules.iter().map(|rule| rule.name()).collect()
+    }
+
+    // Candidates in range accepted by every rule. When the digits must be
+    // non-decreasing, numbers with decreasing digits are jumped over.
+    pub fn matches(&self, range: RangeInclusive<u128>) -> impl Iterator<Item = u128> + '_ {
+        let non_decreasing = self.rules.iter().any(|rule| rule.name() == "non-decreasing");
+        let mut next = if range.is_empty() { None } else { Some(*range.start()) };
+        let end = *range.end();
+        std::iter::from_fn(move || {
+            while let Some(candidate) = next.and_then(|n| if non_decreasing { next_non_decreasing(n) } else { Some(n) }) {
+                if candidate > end {
+                    break;
+                }
+                next = candidate.checked_add(1);
+                if self.explain(candidate).is_none() {
+                    return Some(candidate);
+                }
+            }
+            next = None;
+            None
+        })
+    }
+}
+
+impl Rule for RuleSet {
+    fn name(&self) -> String {
+        self.names().join(", ")
+    }
+
+    fn rejection(&self, digits: &[u8]) -> Option<String> {
+        self.rules.iter().find_map(|rule| rule.rejection(digits).map(|reason| format!("{}: {}", rule.name(), reason)))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{count, PairRule};
+
+    #[test]
+    fn test_rules() {
+        let accepts = |spec: &str, n: u128| parse_rule(spec).unwrap().accepts(&crate::digits(n));
+        assert!(accepts("non-decreasing", 111123));
+        assert!(!accepts("non-decreasing", 223450));
+        assert!(accepts("exact-pair", 111122));
+        assert!(!accepts("exact-pair", 123444));
+        assert!(accepts("run=3+", 123444));
+        assert!(accepts("run[4]=2-3", 123444));
+        assert!(!accepts("run[3]=2-3", 123444));
+        assert!(accepts("length=4-6", 1234));
+        assert!(!accepts("length=6", 1234));
+        assert!(accepts("!pair", 123456));
+        assert!(accepts("length=4|run=5+", 111119));
+        assert!(matches!(parse_rule("palindrome"), Err(RuleError::UnknownRule(_))));
+        assert!(matches!(parse_rule("run=4-2"), Err(RuleError::InvalidBounds(_))));
+        assert!(matches!(parse_rule("run[x]=2"), Err(RuleError::UnknownRule(_))));
+    }
+
+    #[test]
+    fn test_explain() {
+        let rules = RuleSet::parse(&["non-decreasing", "exact-pair", "length=6"]).unwrap();
+        assert_eq!(rules.explain(112233), None);
+        assert_eq!(rules.explain(223450), Some(("non-decreasing".to_string(), "digit 0 at position 6 is lower than 5".to_string())));
+        assert_eq!(rules.explain(123444), Some(("exact-pair".to_string(),
+                   "no run of equal digits with a length of exactly 2 (runs: 1x1, 1x2, 1x3, 3x4)".to_string())));
+        assert_eq!(rules.explain(11122), Some(("length=6".to_string(), "5 digits, expected exactly 6".to_string())));
+    }
+
+    #[test]
+    fn test_matches_digit_dp() {
+        for (specs, rule) in [(["non-decreasing", "pair"], PairRule::SomePair), (["non-decreasing", "exact-pair"], PairRule::ExactPair)].iter() {
+            let rules = RuleSet::parse(specs).unwrap();
+            assert_eq!(rules.matches(248_345..=346_315).count() as u128, count(248_345..=346_315, *rule));
+        }
+    }
+
+    #[test]
+    fn test_toml() {
+        let rules = RuleSet::from_toml("rules = [\"non-decreasing\", \"run[9]=2\"]\n").unwrap();
+        assert_eq!(rules.names(), vec!["non-decreasing", "run[9]=2"]);
+        assert!(matches!(RuleSet::from_toml("rules = [6]"), Err(RuleError::Config(_))));
+        assert!(matches!(RuleSet::from_toml("rule = []"), Err(RuleError::Config(_))));
+    }
+}
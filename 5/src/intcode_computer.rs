@@ -0,0 +1,494 @@
+use std::collections::HashMap;
+use std::fmt;
+use std::fs::File;
+use std::io;
+use std::io::Read;
+use std::str::FromStr;
+
+#[derive(Debug)]
+pub enum ProgramError {
+    Io(io::Error),
+    InvalidUtf8(usize),
+    InvalidToken { index: usize, line: usize, column: usize, token: String }
+}
+
+impl fmt::Display for ProgramError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ProgramError::Io(err) => write!(f, "cannot read program: {}", err),
+            ProgramError::InvalidUtf8(offset) => write!(f, "invalid UTF-8 at byte {}", offset),
+            ProgramError::InvalidToken { index, line, column, token } => {
+                if token.is_empty() {
+                    write!(f, "empty token #{} at line {}, column {}", index, line, column)
+                } else {
+                    write!(f, "invalid token #{} {:?} at line {}, column {}", index, token, line, column)
+                }
+            }
+        }
+    }
+}
+
+impl From<io::Error> for ProgramError {
+    fn from(err: io::Error) -> ProgramError {
+        ProgramError::Io(err)
+    }
+}
+
+// A write into a cell belonging to an instruction that has been or will later
+// be executed.
+#[derive(Clone, Debug, PartialEq)]
+pub struct SelfModification {
+    pub writer: usize,
+    pub target: usize,
+    pub instruction: usize,
+    pub before: String,
+    pub after: String,
+    pub occurrences: u32
+}
+
+impl fmt::Display for SelfModification {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "instruction at {} wrote {} ({} times): instruction at {} changed from \"{}\" to \"{}\"",
+               self.writer, self.target, self.occurrences, self.instruction, self.before, self.after)
+    }
+}
+
+#[derive(Clone)]
+struct SelfModificationDetector {
+    original: Vec<i64>,
+    // executed cell -> address of the instruction it belongs to
+    executed: HashMap<usize, usize>,
+    // written but not yet executed cell -> address of the writing instruction
+    written: HashMap<usize, usize>,
+    reports: Vec<SelfModification>
+}
+
+impl SelfModificationDetector {
+    fn on_fetch(&mut self, memory: &[i64], address: usize) {
+        for cell in address..address + instruction_length(memory[address]) {
+            if let Some(writer) = self.written.remove(&cell) {
+                let before = disassemble(&self.original, address);
+                let after = disassemble(memory, address);
+                self.report(writer, cell, address, before, after);
+            }
+            self.executed.insert(cell, address);
+        }
+    }
+
+    fn on_write(&mut self, memory: &[i64], writer: usize, target: usize, value: i64) {
+        match self.executed.get(&target) {
+            Some(&instruction) => {
+                let end = usize::min(instruction + 4, memory.len());
+                let mut modified = memory[instruction..end].to_vec();
+                modified[target - instruction] = value;
+                let before = disassemble(memory, instruction);
+                let after = disassemble(&modified, 0);
+                self.report(writer, target, instruction, before, after);
+            },
+            None => {
+                self.written.insert(target, writer);
+            }
+        }
+    }
+
+    fn report(&mut self, writer: usize, target: usize, instruction: usize, before: String, after: String) {
+        match self.reports.iter_mut().find(|r| r.writer == writer && r.target == target) {
+            Some(existing) => existing.occurrences += 1,
+            None => self.reports.push(SelfModification {
+                writer,
+                target,
+                instruction,
+                before,
+                after,
+                occurrences: 1
+            })
+        }
+    }
+}
+
+fn instruction_length(full_op_code: i64) -> usize {
+    match full_op_code % 100 {
+        1 | 2 | 7 | 8 => 4,
+        5 | 6 => 3,
+        3 | 4 | 9 => 2,
+        _ => 1
+    }
+}
+
+pub fn disassemble(memory: &[i64], address: usize) -> String {
+    let cell = |offset: usize| memory.get(address + offset).copied().unwrap_or(0);
+    let full_op_code = cell(0);
+    let name = match full_op_code % 100 {
+        1 => "add",
+        2 => "mul",
+        3 => "in",
+        4 => "out",
+        5 => "jnz",
+        6 => "jz",
+        7 => "lt",
+        8 => "eq",
+        9 => "arb",
+        99 => "hlt",
+        _ => return format!("??? {}", full_op_code)
+    };
+    let mut modes = full_op_code / 100;
+    let mut params = Vec::<String>::new();
+    for offset in 1..instruction_length(full_op_code) {
+        let param = cell(offset);
+        params.push(match modes % 10 {
+            0 => format!("[{}]", param),
+            1 => format!("{}", param),
+            2 => format!("[rb{:+}]", param),
+            _ => format!("?{}", param)
+        });
+        modes /= 10;
+    }
+    if params.is_empty() {
+        name.to_string()
+    } else {
+        format!("{} {}", name, params.join(", "))
+    }
+}
+
+#[derive(Clone)]
+pub struct Processor {
+    memory: Vec<i64>,
+    cur: u32,
+    relative_offset: u32,
+    inputs_read: usize,
+    instruction_start: u32,
+    self_modification_detector: Option<SelfModificationDetector>
+}
+
+impl Processor {
+    pub fn init(program_filename: &str) -> Processor {
+        File::open(program_filename)
+            .map_err(ProgramError::from)
+            .and_then(Processor::from_reader)
+            .unwrap_or_else(|err| panic!("Something went wrong reading {}: {}", program_filename, err))
+    }
+
+    pub fn from_reader<R: Read>(mut reader: R) -> Result<Processor, ProgramError> {
+        let mut content = Vec::<u8>::new();
+        reader.read_to_end(&mut content)?;
+        Processor::from_vec(&content)
+    }
+
+    pub fn from_vec(content: &[u8]) -> Result<Processor, ProgramError> {
+        let content = std::str::from_utf8(content)
+            .map_err(|err| ProgramError::InvalidUtf8(err.valid_up_to()))?;
+        content.parse::<Processor>()
+    }
+
+    fn with_memory(memory: Vec<i64>) -> Processor {
+        Processor {
+            memory,
+            cur: 0,
+            relative_offset: 0,
+            inputs_read: 0,
+            instruction_start: 0,
+            self_modification_detector: None
+        }
+    }
+
+    pub fn detect_self_modifications(&mut self) {
+        self.self_modification_detector = Some(SelfModificationDetector {
+            original: self.memory.clone(),
+            executed: HashMap::new(),
+            written: HashMap::new(),
+            reports: Vec::new()
+        });
+    }
+
+    pub fn self_modifications(&self) -> &[SelfModification] {
+        match &self.self_modification_detector {
+            Some(detector) => &detector.reports,
+            None => &[]
+        }
+    }
+    pub fn last_instruction_address(&self) -> usize {
+        self.instruction_start as usize
+    }
+
+    pub fn inputs_read(&self) -> usize {
+        self.inputs_read
+    }
+
+    pub fn process(&mut self, inputs: &Vec<&i64>) -> Option<i64> {
+        let mut input = inputs.iter();
+        loop {
+            self.instruction_start = self.cur;
+            if let Some(detector) = self.self_modification_detector.as_mut() {
+                detector.on_fetch(&self.memory, self.cur as usize);
+            }
+            let full_op_code: i64 = self.memory[Processor::get_from_cursor(&mut self.cur) as usize];
+            let full_op_code: Vec<u8> = format!("{:05}", full_op_code).to_string().as_bytes().iter().map(|b| b - b'0').collect();
+            let mut op_code = full_op_code[full_op_code.len()-1];
+            if full_op_code[full_op_code.len()-2] == 9 {
+                op_code += 90;
+            }
+            let op_code = op_code;
+            let mode_1st = full_op_code[full_op_code.len()-3];
+            let mode_2nd = full_op_code[full_op_code.len()-4];
+            let mode_3rd = full_op_code[full_op_code.len()-5];
+            match op_code {
+                1 => {
+                    let param1: i64 = self.decode_param(mode_1st);
+                    let param2: i64 = self.decode_param(mode_2nd);
+                    let res_index = self.memory[Processor::get_from_cursor(&mut self.cur) as usize];
+                    self.assign_offset(mode_3rd, res_index, param1 + param2);
+                },
+                2 => {
+                    let param1: i64 = self.decode_param(mode_1st);
+                    let param2: i64 = self.decode_param(mode_2nd);
+                    let res_index = self.memory[Processor::get_from_cursor(&mut self.cur) as usize];
+                    self.assign_offset(mode_3rd, res_index, param1 * param2);
+                },
+                3 => {
+                    let param1: i64 = self.memory[Processor::get_from_cursor(&mut self.cur) as usize];
+                    self.assign_offset(mode_1st, param1, **input.next().expect("No input left"));
+                    self.inputs_read += 1;
+                },
+                4 => {
+                    let param1: i64 = self.memory[Processor::get_from_cursor(&mut self.cur) as usize];
+                    let output = match mode_1st {
+                        0 => {
+                            self.memory[param1 as usize]
+                        },
+                        1 => {
+                            param1
+                        },
+                        2 => {
+                            self.memory[(param1+self.relative_offset as i64) as usize]
+                        },
+                        _ => {
+                            panic!("Unsupported mode");
+                        }
+                    };
+                    return Some(output);
+                },
+                5 => {
+                    let param1: i64 = self.decode_param(mode_1st);
+                    let param2: i64 = self.decode_param(mode_2nd);
+                    if param1 != 0 {
+                        self.cur = param2 as u32;
+                    }
+                },
+                6 => {
+                    let param1: i64 = self.decode_param(mode_1st);
+                    let param2: i64 = self.decode_param(mode_2nd);
+                    if param1 == 0 {
+                        self.cur = param2 as u32;
+                    }
+                },
+                7 => {
+                    let param1: i64 = self.decode_param(mode_1st);
+                    let param2: i64 = self.decode_param(mode_2nd);
+                    let param3: i64 = self.memory[Processor::get_from_cursor(&mut self.cur) as usize];
+                    if param1 < param2 {
+                        self.assign_offset(mode_3rd, param3, 1);
+                    } else {
+                        self.assign_offset(mode_3rd, param3, 0);
+                    }
+                },
+                8 => {
+                    let param1: i64 = self.decode_param(mode_1st);
+                    let param2: i64 = self.decode_param(mode_2nd);
+                    let param3: i64 = self.memory[Processor::get_from_cursor(&mut self.cur) as usize];
+                    if param1 == param2 {
+                        self.assign_offset(mode_3rd, param3, 1);
+                    } else {
+                        self.assign_offset(mode_3rd, param3, 0);
+                    }
+                },
+                9 => {
+                    let param1: i64 = self.decode_param(mode_1st);
+                    self.relative_offset = (self.relative_offset as i64 + param1) as u32;
+                },
+                99 => {
+                    return None;
+                },
+                _ => panic!("unknown op code: {}", op_code)
+            }
+        }
+    }
+
+
+    fn translate_offset(&self, mode: u8, offset: i64) -> i64 {
+        match mode {
+            0 => {
+                self.memory[offset as usize]
+            },
+            1 => {
+                offset
+            },
+            2 => {
+                self.relative_offset as i64 + self.memory[offset as usize]
+            },
+            _ => {
+                panic!("Unhandled mode");
+            }
+        }
+    }
+
+    fn assign_offset(&mut self, mode: u8, offset: i64, value: i64) {
+        let real_offset = match mode {
+            0 => {
+                offset as usize
+            },
+            2 => {
+                (self.relative_offset as i64 + offset) as usize
+            },
+            _ => {
+                panic!("Unhandled mode");
+            }
+        };
+        if real_offset >= self.memory.len() {
+            self.memory.resize(real_offset+1, 0);
+        }
+        if let Some(detector) = self.self_modification_detector.as_mut() {
+            detector.on_write(&self.memory, self.instruction_start as usize, real_offset, value);
+        }
+        self.memory[real_offset] = value;
+    }
+
+    fn decode_param(&mut self, mode: u8) -> i64 {
+        let cur = Processor::get_from_cursor(&mut self.cur);
+        let offset = self.translate_offset(mode, cur as i64) as usize;
+        if offset < self.memory.len() {
+            self.memory[offset]
+        } else {
+            0
+        }
+    }
+
+    fn get_from_cursor(cursor: &mut u32) -> u32 {
+        let ret = *cursor;
+        *cursor += 1;
+        ret
+    }
+}
+
+impl FromStr for Processor {
+    type Err = ProgramError;
+
+    // Tokens are comma separated and may be surrounded by whitespace (including
+    // newlines), but must not be empty nor contain whitespace themselves.
+    fn from_str(content: &str) -> Result<Processor, ProgramError> {
+        let mut memory = Vec::<i64>::new();
+        let mut offset = 0;
+        for (index, raw_token) in content.split(',').enumerate() {
+            let token = raw_token.trim();
+            let token_offset = if token.is_empty() {
+                offset
+            } else {
+                offset + raw_token.find(token).unwrap()
+            };
+            match token.parse::<i64>() {
+                Ok(value) => memory.push(value),
+                Err(_) => {
+                    let before = &content[..token_offset];
+                    let line = before.matches('\n').count() + 1;
+                    let column = before.len() - before.rfind('\n').map_or(0, |pos| pos + 1) + 1;
+                    return Err(ProgramError::InvalidToken {
+                        index,
+                        line,
+                        column,
+                        token: token.to_string()
+                    });
+                }
+            }
+            offset += raw_token.len() + 1;
+        }
+        Ok(Processor::with_memory(memory))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn run(program: &str) -> Vec<i64> {
+        let mut computer = program.parse::<Processor>().unwrap();
+        computer.process(&vec!());
+        computer.memory
+    }
+
+    #[test]
+    fn test_from_str() {
+        assert_eq!(run("1,9,10,3,2,3,11,0,99,30,40,50"), [3500, 9, 10, 70, 2, 3, 11, 0, 99, 30, 40, 50]);
+        assert_eq!(run("1,1,1,4,99,5,6,0,99\n"), [30, 1, 1, 4, 2, 5, 6, 0, 99]);
+        assert_eq!(run(" 1101, 100,\n-1,4,0\r\n"), [1101, 100, -1, 4, 99]);
+    }
+
+    #[test]
+    fn test_from_reader_and_vec() {
+        let program = "2,4,4,5,99,0\n";
+        assert_eq!(Processor::from_reader(program.as_bytes()).unwrap().memory, [2, 4, 4, 5, 99, 0]);
+        assert_eq!(Processor::from_vec(program.as_bytes()).unwrap().memory, [2, 4, 4, 5, 99, 0]);
+    }
+
+    fn error_position(program: &str) -> (usize, usize, usize, String) {
+        match program.parse::<Processor>() {
+            Err(ProgramError::InvalidToken { index, line, column, token }) => (index, line, column, token),
+            Err(err) => panic!("unexpected error {}", err),
+            Ok(_) => panic!("{:?} should not parse", program)
+        }
+    }
+
+    #[test]
+    fn test_invalid_tokens() {
+        assert_eq!(error_position("1,2,x,99"), (2, 1, 5, "x".to_string()));
+        assert_eq!(error_position("1,2,\n3,,99"), (3, 2, 3, "".to_string()));
+        assert_eq!(error_position("1,2,99,\n"), (3, 1, 8, "".to_string()));
+        assert_eq!(error_position("1,2\n3,99"), (1, 1, 3, "2\n3".to_string()));
+        assert_eq!(error_position("1, 2 3"), (1, 1, 4, "2 3".to_string()));
+        assert_eq!(error_position(""), (0, 1, 1, "".to_string()));
+    }
+
+    #[test]
+    fn test_self_modification_before_execution() {
+        let mut computer = "1101,0,99,4,1,0,0,0".parse::<Processor>().unwrap();
+        computer.detect_self_modifications();
+        computer.process(&vec!());
+        assert_eq!(computer.self_modifications(), [SelfModification {
+            writer: 0,
+            target: 4,
+            instruction: 4,
+            before: "add [0], [0], [0]".to_string(),
+            after: "hlt".to_string(),
+            occurrences: 1
+        }]);
+    }
+
+    #[test]
+    fn test_self_modification_after_execution() {
+        let mut computer = "1001,1,1,1,99".parse::<Processor>().unwrap();
+        computer.detect_self_modifications();
+        computer.process(&vec!());
+        assert_eq!(computer.self_modifications(), [SelfModification {
+            writer: 0,
+            target: 1,
+            instruction: 0,
+            before: "add [1], 1, [1]".to_string(),
+            after: "add [2], 1, [1]".to_string(),
+            occurrences: 1
+        }]);
+    }
+
+    #[test]
+    fn test_no_self_modification() {
+        let mut computer = "1,9,10,11,2,11,11,9,99,30,40,50".parse::<Processor>().unwrap();
+        computer.detect_self_modifications();
+        computer.process(&vec!());
+        assert!(computer.self_modifications().is_empty());
+    }
+
+    #[test]
+    fn test_invalid_utf8() {
+        match Processor::from_vec(&[b'1', b',', 0xff]) {
+            Err(ProgramError::InvalidUtf8(offset)) => assert_eq!(offset, 2),
+            _ => panic!("invalid UTF-8 should be reported")
+        }
+    }
+}
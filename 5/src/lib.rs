@@ -0,0 +1,143 @@
+use std::fmt;
+
+pub mod intcode_computer;
+
+use intcode_computer::Processor;
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct DiagnosticOutput {
+    pub index: usize,
+    pub address: usize,
+    pub value: i64
+}
+
+impl DiagnosticOutput {
+    fn to_json(self) -> String {
+        format!("{{\"index\":{},\"address\":{},\"value\":{}}}", self.index, self.address, self.value)
+    }
+}
+
+// Outputs of the TEST program: every output but the last one is a test
+// result which must be 0, the last one is the diagnostic code.
+#[derive(Clone, Debug, PartialEq)]
+pub struct DiagnosticReport {
+    pub system_id: i64,
+    pub outputs: Vec<DiagnosticOutput>
+}
+
+impl DiagnosticReport {
+    pub fn run(processor: &Processor, system_id: i64) -> DiagnosticReport {
+        let mut processor = processor.clone();
+        let mut outputs = Vec::<DiagnosticOutput>::new();
+        // the system ID is passed until it is read, later reads find no input
+        let system_id_input = vec!(&system_id);
+        let no_input = vec!();
+        loop {
+            let inputs = if processor.inputs_read() == 0 { &system_id_input } else { &no_input };
+            match processor.process(inputs) {
+                Some(value) => outputs.push(DiagnosticOutput {
+                    index: outputs.len(),
+                    address: processor.last_instruction_address(),
+                    value
+                }),
+                None => break
+            }
+        }
+        DiagnosticReport {
+            system_id,
+            outputs
+        }
+    }
+
+    fn tests(&self) -> &[DiagnosticOutput] {
+        match self.outputs.split_last() {
+            Some((_, tests)) => tests,
+            None => &[]
+        }
+    }
+
+    pub fn failures(&self) -> Vec<DiagnosticOutput> {
+        self.tests().iter().filter(|output| output.value != 0).copied().collect()
+    }
+
+    pub fn diagnostic_code(&self) -> Option<DiagnosticOutput> {
+        self.outputs.last().copied()
+    }
+
+    pub fn passed(&self) -> bool {
+        self.diagnostic_code().is_some() && self.failures().is_empty()
+    }
+
+    pub fn to_json(&self) -> String {
+        let outputs: Vec<String> = self.tests().iter().map(|output| output.to_json()).collect();
+        let failures: Vec<String> = self.failures().iter().map(|output| output.to_json()).collect();
+        let diagnostic_code = match self.diagnostic_code() {
+            Some(output) => output.to_json(),
+            None => "null".to_string()
+        };
+        format!("{{\"system_id\":{},\"tests\":[{}],\"failures\":[{}],\"diagnostic_code\":{},\"passed\":{}}}",
+                self.system_id, outputs.join(","), failures.join(","), diagnostic_code, self.passed())
+    }
+}
+
+impl fmt::Display for DiagnosticReport {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(f, "System ID: {}", self.system_id)?;
+        for output in self.tests() {
+            let status = if output.value == 0 { "ok" } else { "FAILED" };
+            writeln!(f, "Test {} (instruction {}): {} {}", output.index, output.address, output.value, status)?;
+        }
+        match self.diagnostic_code() {
+            Some(output) => writeln!(f, "Diagnostic code: {} (instruction {})", output.value, output.address)?,
+            None => writeln!(f, "Diagnostic code: none, the program produced no output")?
+        }
+        let failures = self.failures();
+        if self.passed() {
+            write!(f, "Result: passed")
+        } else if failures.is_empty() {
+            write!(f, "Result: FAILED")
+        } else {
+            let indexes: Vec<String> = failures.iter().map(|output| output.index.to_string()).collect();
+            write!(f, "Result: FAILED (tests {})", indexes.join(", "))
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_equal_to_8() {
+        let processor = "3,9,8,9,10,9,4,9,99,-1,8".parse::<Processor>().unwrap();
+        assert_eq!(DiagnosticReport::run(&processor, 8).diagnostic_code(), Some(DiagnosticOutput { index: 0, address: 6, value: 1 }));
+        assert_eq!(DiagnosticReport::run(&processor, 7).diagnostic_code(), Some(DiagnosticOutput { index: 0, address: 6, value: 0 }));
+    }
+
+    #[test]
+    #[should_panic(expected = "No input left")]
+    fn test_single_input() {
+        let processor = "3,11,4,11,3,12,4,12,99,0,0,0,0".parse::<Processor>().unwrap();
+        DiagnosticReport::run(&processor, 1);
+    }
+
+    #[test]
+    fn test_output_before_input() {
+        let processor = "104,0,3,9,4,9,99,0,0,0".parse::<Processor>().unwrap();
+        let report = DiagnosticReport::run(&processor, 5);
+        assert!(report.passed());
+        assert_eq!(report.diagnostic_code(), Some(DiagnosticOutput { index: 1, address: 4, value: 5 }));
+    }
+
+    #[test]
+    fn test_failing_test() {
+        let processor = "3,13,104,0,4,13,104,0,104,42,99".parse::<Processor>().unwrap();
+        let report = DiagnosticReport::run(&processor, 5);
+        assert!(!report.passed());
+        assert_eq!(report.failures(), [DiagnosticOutput { index: 1, address: 4, value: 5 }]);
+        assert_eq!(report.to_json(), "{\"system_id\":5,\"tests\":[{\"index\":0,\"address\":2,\"value\":0},\
+            {\"index\":1,\"address\":4,\"value\":5},{\"index\":2,\"address\":6,\"value\":0}],\
+            \"failures\":[{\"index\":1,\"address\":4,\"value\":5}],\
+            \"diagnostic_code\":{\"index\":3,\"address\":8,\"value\":42},\"passed\":false}");
+    }
+}
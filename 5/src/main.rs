@@ -0,0 +1,41 @@
+use std::env;
+use std::process;
+
+use diagnostic::intcode_computer::Processor;
+use diagnostic::DiagnosticReport;
+
+fn main() {
+    let args:Vec<String> = env::args().collect();
+    let mut program_filename = "input.txt".to_string();
+    let mut json = false;
+    let mut system_id: Option<i64> = None;
+    let mut it = args.iter().skip(1);
+    while let Some(arg) = it.next() {
+        match arg.as_str() {
+            "--program" => program_filename = it.next().expect("Missing program file name").clone(),
+            "--format" => {
+                json = match it.next().map(String::as_str) {
+                    Some("json") => true,
+                    Some("text") => false,
+                    _ => panic!("Format must be text or json")
+                }
+            },
+            _ => system_id = Some(arg.parse::<i64>().expect("Invalid system ID"))
+        }
+    }
+    let system_id = match system_id {
+        Some(system_id) => system_id,
+        None => panic!("Syntax : {} [--program <file>] [--format text|json] <system ID>",args[0])
+    };
+
+    let processor = Processor::init(&program_filename);
+    let report = DiagnosticReport::run(&processor, system_id);
+    if json {
+        println!("{}", report.to_json());
+    } else {
+        println!("{}", report);
+    }
+    if !report.passed() {
+        process::exit(1);
+    }
+}
@@ -0,0 +1,158 @@
+use crate::OrbitMap;
+
+// Objects visible once subtrees larger than collapse_above are folded. The
+// objects of the highlighted path and their ancestors always stay unfolded.
+struct View<'a> {
+    map: &'a OrbitMap,
+    on_path: Vec<bool>,
+    unfolded: Vec<bool>,
+    collapse_above: Option<usize>
+}
+
+impl<'a> View<'a> {
+    fn new(map: &'a OrbitMap, path: Option<(usize, usize)>, collapse_above: Option<usize>) -> View<'a> {
+        let mut on_path = vec![false; map.len()];
+        let mut unfolded = vec![false; map.len()];
+        if let Some(path) = path.and_then(|(first, second)| map.path(first, second)) {
+            for id in path {
+                on_path[id] = true;
+                let mut cur = Some(id);
+                while let Some(id) = cur.filter(|id| !unfolded[*id]) {
+                    unfolded[id] = true;
+                    cur = map.parent(id);
+                }
+            }
+        }
+        View { map, on_path, unfolded, collapse_above }
+    }
+
+    fn is_collapsed(&self, id: usize) -> bool {
+        !self.unfolded[id]
+            && !self.map.children(id).is_empty()
+            && self.collapse_above.map_or(false, |size| self.map.subtree_size(id) > size)
+    }
+
+    fn annotation(&self, id: usize) -> String {
+        let collapsed = if self.is_collapsed(id) { ", collapsed" } else { "" };
+        format!("depth {}, size {}{}", self.map.depth(id), self.map.subtree_size(id), collapsed)
+    }
+
+    // Visible objects in depth first order, children sorted by name.
+    fn visible(&self) -> Vec<usize> {
+        let mut visible = Vec::<usize>::new();
+        let mut stack: Vec<usize> = self.map.roots().into_iter().rev().collect();
+        while let Some(id) = stack.pop() {
+            visible.push(id);
+            if !self.is_collapsed(id) {
+                stack.extend(self.map.children(id).iter().rev());
+            }
+        }
+        visible
+    }
+}
+
+fn quote(name: &str) -> String {
+    format!("\"{}\"", name.replace('\\', "\\\\").replace('"', "\\\"").replace('\n', "\\n"))
+}
+
+// Graphviz digraph, edges going from centers to the objects orbiting them.
+// The path between the two objects of path is drawn in red.
+pub fn to_dot(map: &OrbitMap, path: Option<(usize, usize)>, collapse_above: Option<usize>) -> String {
+    let view = View::new(map, path, collapse_above);
+    let visible = view.visible();
+    let mut dot = String::from("digraph orbits {\n");
+    for id in visible.iter() {
+        let mut attributes = vec![format!("label={}", quote(&format!("{}\n{}", map.name(*id), view.annotation(*id))))];
+        if view.is_collapsed(*id) {
+            attributes.push("shape=box".to_string());
+        }
+        if view.on_path[*id] {
+            attributes.push("color=red".to_string());
+            attributes.push("fontcolor=red".to_string());
+        }
+        dot += &format!("  {} [{}];\n", quote(map.name(*id)), attributes.join(", "));
+    }
+    for id in visible {
+        if let Some(parent) = map.parent(id) {
+            let highlight = if view.on_path[id] && view.on_path[parent] { " [color=red, penwidth=2]" } else { "" };
+            dot += &format!("  {} -> {}{};\n", quote(map.name(parent)), quote(map.name(id)), highlight);
+        }
+    }
+    dot += "}\n";
+    dot
+}
+
+// Indented tree, one object per line, objects of the path being starred.
+pub fn to_tree(map: &OrbitMap, path: Option<(usize, usize)>, collapse_above: Option<usize>) -> String {
+    let view = View::new(map, path, collapse_above);
+    let mut tree = String::new();
+    // object, prefix of its line, prefix of the lines of its children
+    let mut stack: Vec<(usize, String, String)> = map.roots().into_iter().rev()
+        .map(|root| (root, String::new(), String::new()))
+        .collect();
+    while let Some((id, prefix, children_prefix)) = stack.pop() {
+        let star = if view.on_path[id] { " *" } else { "" };
+        tree += &format!("{}{} ({}){}\n", prefix, map.name(id), view.annotation(id), star);
+        if view.is_collapsed(id) {
+            continue;
+        }
+        let children = map.children(id);
+        for (index, child) in children.iter().enumerate().rev() {
+            let (branch, indent) = if index + 1 == children.len() { ("└── ", "    ") } else { ("├── ", "│   ") };
+            stack.push((*child, format!("{}{}", children_prefix, branch), format!("{}{}", children_prefix, indent)));
+        }
+    }
+    tree
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::fs;
+
+    fn load() -> OrbitMap {
+        OrbitMap::parse(&fs::read_to_string("test1.in").unwrap(), None).ok().unwrap()
+    }
+
+    #[test]
+    fn test_tree() {
+        let map = load();
+        let tree = to_tree(&map, None, Some(3));
+        assert_eq!(tree, "COM (depth 0, size 14, collapsed)\n");
+        let path = Some((map.id("YOU").unwrap(), map.id("SAN").unwrap()));
+        let tree = to_tree(&map, path, Some(3));
+        assert_eq!(tree.lines().collect::<Vec<&str>>(), vec![
+            "COM (depth 0, size 14)",
+            "└── B (depth 1, size 13)",
+            "    ├── C (depth 2, size 10)",
+            "    │   └── D (depth 3, size 9) *",
+            "    │       ├── E (depth 4, size 6) *",
+            "    │       │   ├── F (depth 5, size 1)",
+            "    │       │   └── J (depth 5, size 4) *",
+            "    │       │       └── K (depth 6, size 3) *",
+            "    │       │           ├── L (depth 7, size 1)",
+            "    │       │           └── YOU (depth 7, size 1) *",
+            "    │       └── I (depth 4, size 2) *",
+            "    │           └── SAN (depth 5, size 1) *",
+            "    └── G (depth 2, size 2)",
+            "        └── H (depth 3, size 1)"
+        ]);
+        assert_eq!(to_tree(&map, None, None).lines().count(), 14);
+    }
+
+    #[test]
+    fn test_dot() {
+        let map = load();
+        let path = Some((map.id("YOU").unwrap(), map.id("SAN").unwrap()));
+        let dot = to_dot(&map, path, None);
+        assert!(dot.starts_with("digraph orbits {\n"));
+        assert!(dot.contains("  \"K\" -> \"YOU\" [color=red, penwidth=2];\n"));
+        assert!(dot.contains("  \"D\" -> \"I\" [color=red, penwidth=2];\n"));
+        assert!(dot.contains("  \"C\" -> \"D\";\n"));
+        assert!(dot.contains("  \"SAN\" [label=\"SAN\\ndepth 5, size 1\", color=red, fontcolor=red];\n"));
+        assert_eq!(dot.matches(" -> ").count(), 13);
+        let dot = to_dot(&map, None, Some(5));
+        assert!(dot.contains("  \"COM\" [label=\"COM\\ndepth 0, size 14, collapsed\", shape=box];\n"));
+        assert_eq!(dot.matches(" -> ").count(), 0);
+    }
+}
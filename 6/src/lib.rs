@@ -0,0 +1,479 @@
+use std::collections::HashMap;
+use std::fmt;
+
+pub mod export;
+
+#[derive(Debug, PartialEq)]
+pub enum MapError {
+    MalformedRoute { line: usize, text: String },
+    TwoParents { line: usize, object: String, center: String, first_line: usize, first_center: String },
+    Cycle { lines: Vec<usize>, objects: Vec<String> },
+    MultipleRoots { roots: Vec<(String, usize)> },
+    DanglingReference { line: usize, object: String, center: String },
+    MissingRoot { root: String }
+}
+
+impl fmt::Display for MapError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            MapError::MalformedRoute { line, text } => write!(f, "line {}: malformed route {:?}, expected <center>)<object>", line, text),
+            MapError::TwoParents { line, object, center, first_line, first_center } =>
+                write!(f, "line {}: {} cannot orbit {}, it already orbits {} (line {})", line, object, center, first_center, first_line),
+            MapError::Cycle { lines, objects } => {
+                let lines: Vec<String> = lines.iter().map(usize::to_string).collect();
+                write!(f, "lines {}: orbit cycle {}){}", lines.join(", "), objects.join(")"), objects[0])
+            },
+            MapError::MultipleRoots { roots } => {
+                let roots: Vec<String> = roots.iter().map(|(root, line)| format!("{} (line {})", root, line)).collect();
+                write!(f, "several objects orbit nothing: {}", roots.join(", "))
+            },
+            MapError::DanglingReference { line, object, center } => write!(f, "line {}: {} orbits {}, which orbits nothing", line, object, center),
+            MapError::MissingRoot { root } => write!(f, "root {} is not in the map", root)
+        }
+    }
+}
+
+fn sort_errors(errors: &mut [MapError]) {
+    errors.sort_by_key(|err| match err {
+        MapError::MalformedRoute { line, .. } | MapError::TwoParents { line, .. } | MapError::DanglingReference { line, .. } => *line,
+        MapError::Cycle { lines, .. } => *lines.iter().min().unwrap(),
+        MapError::MultipleRoots { .. } | MapError::MissingRoot { .. } => usize::MAX
+    });
+}
+
+// Objects are interned: each name gets an id, index in every per-object vector.
+// Ancestors at every power of two distance are precomputed, so that common
+// ancestors and distances are found in O(log n).
+pub struct OrbitMap {
+    names: Vec<String>,
+    ids: HashMap<String, usize>,
+    parents: Vec<Option<usize>>,
+    depths: Vec<u32>,
+    // ancestors[k][id]: ancestor 2^k levels above id, or its root
+    ancestors: Vec<Vec<usize>>,
+    // children sorted by name, and number of objects of each subtree
+    children: Vec<Vec<usize>>,
+    sizes: Vec<usize>
+}
+
+impl OrbitMap {
+    // Routes are (center, object) pairs, object being in orbit around center.
+    // Objects orbiting two centers and cycles are reported as in parse, routes
+    // being numbered from 1. Objects orbiting nothing are roots of separate
+    // trees.
+    pub fn from_routes<S: AsRef<str>>(routes: &[(S, S)]) -> Result<OrbitMap, Vec<MapError>> {
+        let routes = routes.iter().enumerate().map(|(index, (center, object))| (index + 1, center.as_ref(), object.as_ref()));
+        let mut errors = Vec::<MapError>::new();
+        let (map, _, _) = OrbitMap::link(routes, &mut errors);
+        if !errors.is_empty() {
+            sort_errors(&mut errors);
+            return Err(errors);
+        }
+        Ok(map.complete())
+    }
+
+    // One <center>)<object> route per line, blank lines being ignored. Every
+    // problem is reported: malformed lines, objects orbiting two centers,
+    // cycles, and objects orbiting nothing, other than root when given.
+    pub fn parse(content: &str, root: Option<&str>) -> Result<OrbitMap, Vec<MapError>> {
+        let mut errors = Vec::<MapError>::new();
+        let mut routes = Vec::<(usize, &str, &str)>::new();
+        for (index, text) in content.lines().enumerate() {
+            let line = index + 1;
+            if text.trim().is_empty() {
+                continue;
+            }
+            match text.trim().split_once(')') {
+                Some((center, object)) if !center.is_empty() && !object.is_empty() && !object.contains(')') => routes.push((line, center, object)),
+                _ => errors.push(MapError::MalformedRoute { line, text: text.to_string() })
+            }
+        }
+        let (map, route_lines, first_lines) = OrbitMap::link(routes.into_iter(), &mut errors);
+
+        let roots: Vec<usize> = (0..map.len()).filter(|id| map.parents[*id].is_none()).collect();
+        match root {
+            Some(root) => {
+                if map.id(root).is_none() {
+                    errors.push(MapError::MissingRoot { root: root.to_string() });
+                }
+                for (object, center) in map.parents.iter().enumerate().filter_map(|(id, parent)| parent.map(|parent| (id, parent))) {
+                    if map.parents[center].is_none() && map.names[center] != root {
+                        errors.push(MapError::DanglingReference {
+                            line: route_lines[object],
+                            object: map.names[object].clone(),
+                            center: map.names[center].clone()
+                        });
+                    }
+                }
+            },
+            None if roots.len() > 1 => errors.push(MapError::MultipleRoots {
+                roots: roots.iter().map(|id| (map.names[*id].clone(), first_lines[*id])).collect()
+            }),
+            None => ()
+        }
+
+        if !errors.is_empty() {
+            sort_errors(&mut errors);
+            return Err(errors);
+        }
+        Ok(map.complete())
+    }
+
+    // Links the numbered routes, reporting objects orbiting two centers and
+    // cycles. Also returns the line of the route of each object, and the line
+    // where it is first seen.
+    fn link<'a, I: Iterator<Item = (usize, &'a str, &'a str)>>(routes: I, errors: &mut Vec<MapError>) -> (OrbitMap, Vec<usize>, Vec<usize>) {
+        let mut map = OrbitMap::empty();
+        let mut route_lines = Vec::<usize>::new();
+        let mut first_lines = Vec::<usize>::new();
+        for (line, center, object) in routes {
+            let center = map.intern(center);
+            let object = map.intern(object);
+            route_lines.resize(map.len(), 0);
+            first_lines.resize(map.len(), line);
+            match map.parents[object] {
+                Some(first_center) if first_center != center => errors.push(MapError::TwoParents {
+                    line,
+                    object: map.names[object].clone(),
+                    center: map.names[center].clone(),
+                    first_line: route_lines[object],
+                    first_center: map.names[first_center].clone()
+                }),
+                Some(_) => (),
+                None => {
+                    map.parents[object] = Some(center);
+                    route_lines[object] = line;
+                }
+            }
+        }
+
+        for cycle in map.find_cycles() {
+            let mut lines: Vec<usize> = cycle.iter().map(|id| route_lines[*id]).collect();
+            lines.sort_unstable();
+            // centers first, as in routes
+            errors.push(MapError::Cycle { lines, objects: cycle.iter().rev().map(|id| map.names[*id].clone()).collect() });
+        }
+        (map, route_lines, first_lines)
+    }
+
+    // Only called on maps without cycles.
+    fn complete(mut self) -> OrbitMap {
+        self.compute_depths();
+        self.compute_ancestors();
+        self.compute_subtrees();
+        self
+    }
+
+    fn empty() -> OrbitMap {
+        OrbitMap {
+            names: Vec::new(),
+            ids: HashMap::new(),
+            parents: Vec::new(),
+            depths: Vec::new(),
+            ancestors: Vec::new(),
+            children: Vec::new(),
+            sizes: Vec::new()
+        }
+    }
+
+    // Each cycle once, in linear time: objects are walked towards their root,
+    // stopping on objects seen in a previous walk. Meeting an object of the
+    // current walk closes a cycle.
+    fn find_cycles(&self) -> Vec<Vec<usize>> {
+        let mut walks: Vec<Option<usize>> = vec![None; self.len()];
+        let mut cycles = Vec::<Vec<usize>>::new();
+        for start in 0..self.len() {
+            let mut path = Vec::<usize>::new();
+            let mut cur = Some(start);
+            while let Some(id) = cur {
+                match walks[id] {
+                    Some(walk) if walk == start => {
+                        let position = path.iter().position(|other| *other == id).unwrap();
+                        cycles.push(path[position..].to_vec());
+                        break;
+                    },
+                    Some(_) => break,
+                    None => {
+                        walks[id] = Some(start);
+                        path.push(id);
+                        cur = self.parents[id];
+                    }
+                }
+            }
+        }
+        cycles
+    }
+
+    fn intern(&mut self, name: &str) -> usize {
+        if let Some(id) = self.ids.get(name) {
+            return *id;
+        }
+        let id = self.names.len();
+        self.names.push(name.to_string());
+        self.ids.insert(name.to_string(), id);
+        self.parents.push(None);
+        id
+    }
+
+    // Memoised: every object is visited once, stacking the ancestors whose
+    // depth is not known yet.
+    fn compute_depths(&mut self) {
+        let mut depths: Vec<Option<u32>> = vec![None; self.names.len()];
+        for id in 0..self.names.len() {
+            let mut stack = Vec::<usize>::new();
+            let mut cur = id;
+            let mut depth = loop {
+                if let Some(depth) = depths[cur] {
+                    break depth;
+                }
+                stack.push(cur);
+                match self.parents[cur] {
+                    Some(parent) => cur = parent,
+                    None => {
+                        depths[stack.pop().unwrap()] = Some(0);
+                        break 0;
+                    }
+                }
+            };
+            while let Some(cur) = stack.pop() {
+                depth += 1;
+                depths[cur] = Some(depth);
+            }
+        }
+        self.depths = depths.into_iter().map(Option::unwrap).collect();
+    }
+
+    fn compute_ancestors(&mut self) {
+        let max_depth = self.depths.iter().copied().max().unwrap_or(0);
+        let levels = (32 - max_depth.leading_zeros()).max(1) as usize;
+        let first: Vec<usize> = (0..self.names.len()).map(|id| self.parents[id].unwrap_or(id)).collect();
+        self.ancestors = vec![first];
+        for k in 1..levels {
+            let previous = &self.ancestors[k - 1];
+            let next = (0..self.names.len()).map(|id| previous[previous[id]]).collect();
+            self.ancestors.push(next);
+        }
+    }
+
+    // Deepest objects first, so that every subtree size is complete before
+    // being added to its parent.
+    fn compute_subtrees(&mut self) {
+        self.children = vec![Vec::new(); self.names.len()];
+        for (id, parent) in self.parents.iter().enumerate() {
+            if let Some(parent) = parent {
+                self.children[*parent].push(id);
+            }
+        }
+        let names = &self.names;
+        for children in self.children.iter_mut() {
+            children.sort_by(|a, b| names[*a].cmp(&names[*b]));
+        }
+        let mut by_depth: Vec<usize> = (0..self.names.len()).collect();
+        by_depth.sort_unstable_by_key(|id| std::cmp::Reverse(self.depths[*id]));
+        self.sizes = vec![1; self.names.len()];
+        for id in by_depth {
+            if let Some(parent) = self.parents[id] {
+                self.sizes[parent] += self.sizes[id];
+            }
+        }
+    }
+
+    pub fn len(&self) -> usize {
+        self.names.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.names.is_empty()
+    }
+
+    pub fn id(&self, name: &str) -> Option<usize> {
+        self.ids.get(name).copied()
+    }
+
+    pub fn name(&self, id: usize) -> &str {
+        &self.names[id]
+    }
+
+    pub fn parent(&self, id: usize) -> Option<usize> {
+        self.parents[id]
+    }
+
+    pub fn children(&self, id: usize) -> &[usize] {
+        &self.children[id]
+    }
+
+    // Objects orbiting nothing, sorted by name.
+    pub fn roots(&self) -> Vec<usize> {
+        let mut roots: Vec<usize> = (0..self.names.len()).filter(|id| self.parents[*id].is_none()).collect();
+        roots.sort_by(|a, b| self.names[*a].cmp(&self.names[*b]));
+        roots
+    }
+
+    // Number of objects of the subtree, the object included.
+    pub fn subtree_size(&self, id: usize) -> usize {
+        self.sizes[id]
+    }
+
+    // Number of direct and indirect orbits of the object.
+    pub fn depth(&self, id: usize) -> u32 {
+        self.depths[id]
+    }
+
+    // Total number of direct and indirect orbits, from the memoised depths.
+    pub fn total_orbits(&self) -> u64 {
+        self.depths.iter().map(|depth| u64::from(*depth)).sum()
+    }
+
+    fn ancestor(&self, id: usize, levels: u32) -> usize {
+        let mut cur = id;
+        for (k, ancestors) in self.ancestors.iter().enumerate() {
+            if levels & (1 << k) != 0 {
+                cur = ancestors[cur];
+            }
+        }
+        cur
+    }
+
+    // Lowest common ancestor, an object being its own ancestor. None when the
+    // objects are in different trees.
+    pub fn common_ancestor(&self, first: usize, second: usize) -> Option<usize> {
+        let (deeper, other) = if self.depths[first] >= self.depths[second] { (first, second) } else { (second, first) };
+        let mut first = self.ancestor(deeper, self.depths[deeper] - self.depths[other]);
+        let mut second = other;
+        if first == second {
+            return Some(first);
+        }
+        for ancestors in self.ancestors.iter().rev() {
+            if ancestors[first] != ancestors[second] {
+                first = ancestors[first];
+                second = ancestors[second];
+            }
+        }
+        match (self.parents[first], self.parents[second]) {
+            (Some(parent), Some(other)) if parent == other => Some(parent),
+            _ => None
+        }
+    }
+
+    // Number of orbit links between two objects.
+    pub fn distance(&self, first: usize, second: usize) -> Option<u32> {
+        let ancestor = self.common_ancestor(first, second)?;
+        Some(self.depths[first] + self.depths[second] - 2 * self.depths[ancestor])
+    }
+
+    // Objects from first to second, both included, through their common
+    // ancestor.
+    pub fn path(&self, first: usize, second: usize) -> Option<Vec<usize>> {
+        let ancestor = self.common_ancestor(first, second)?;
+        let mut path = vec![first];
+        while *path.last().unwrap() != ancestor {
+            path.push(self.parents[*path.last().unwrap()].unwrap());
+        }
+        let mut down = Vec::<usize>::new();
+        let mut cur = second;
+        while cur != ancestor {
+            down.push(cur);
+            cur = self.parents[cur].unwrap();
+        }
+        path.extend(down.into_iter().rev());
+        Some(path)
+    }
+
+    // Orbital transfers needed for the first object to orbit the same object
+    // as the second one.
+    pub fn transfers(&self, first: usize, second: usize) -> Option<u32> {
+        self.distance(self.parents[first]?, self.parents[second]?)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::fs;
+
+    fn load(filename: &str) -> OrbitMap {
+        let content = fs::read_to_string(filename).unwrap();
+        let routes: Vec<(&str, &str)> = content.lines().map(|line| {
+            let mut route = line.split(')');
+            (route.next().unwrap(), route.next().unwrap())
+        }).collect();
+        OrbitMap::from_routes(&routes).unwrap()
+    }
+
+    #[test]
+    fn test_depths() {
+        let map = load("test1.in");
+        assert_eq!(map.len(), 14);
+        assert_eq!(map.depth(map.id("COM").unwrap()), 0);
+        assert_eq!(map.depth(map.id("L").unwrap()), 7);
+        assert_eq!(map.depth(map.id("YOU").unwrap()), 7);
+    }
+
+    #[test]
+    fn test_common_ancestor() {
+        let map = load("test1.in");
+        let id = |name: &str| map.id(name).unwrap();
+        assert_eq!(map.common_ancestor(id("YOU"), id("SAN")), Some(id("D")));
+        assert_eq!(map.common_ancestor(id("L"), id("E")), Some(id("E")));
+        assert_eq!(map.common_ancestor(id("H"), id("COM")), Some(id("COM")));
+        assert_eq!(map.distance(id("H"), id("L")), Some(8));
+        assert_eq!(map.transfers(id("YOU"), id("SAN")), Some(4));
+        assert_eq!(map.transfers(id("COM"), id("SAN")), None);
+    }
+
+    #[test]
+    fn test_total_orbits() {
+        let map = OrbitMap::parse(&fs::read_to_string("test1.in").unwrap(), Some("COM")).unwrap();
+        assert_eq!(map.total_orbits(), 54);
+        let map = OrbitMap::parse("COM)B\nB)C\nC)D\nD)E\nE)F\nB)G\nG)H\nD)I\nE)J\nJ)K\nK)L\n", None).unwrap();
+        assert_eq!(map.total_orbits(), 42);
+    }
+
+    #[test]
+    fn test_validation() {
+        let errors = OrbitMap::parse("COM)A\nA)B\nB-C\nC)D\nD)E\nE)C\nX)B\nZ)Y\n\nA)", Some("COM")).err().unwrap();
+        let messages: Vec<String> = errors.iter().map(|err| err.to_string()).collect();
+        assert_eq!(messages, vec![
+            "line 3: malformed route \"B-C\", expected <center>)<object>",
+            "lines 4, 5, 6: orbit cycle D)E)C)D",
+            "line 7: B cannot orbit X, it already orbits A (line 2)",
+            "line 8: Y orbits Z, which orbits nothing",
+            "line 10: malformed route \"A)\", expected <center>)<object>"
+        ]);
+        let errors = OrbitMap::parse("COM)A\nB)C\n", None).err().unwrap();
+        assert_eq!(errors, vec![MapError::MultipleRoots { roots: vec![("COM".to_string(), 1), ("B".to_string(), 2)] }]);
+        let errors = OrbitMap::parse("A)B\n", Some("COM")).err().unwrap();
+        assert_eq!(errors, vec![
+            MapError::DanglingReference { line: 1, object: "B".to_string(), center: "A".to_string() },
+            MapError::MissingRoot { root: "COM".to_string() }
+        ]);
+    }
+
+    #[test]
+    fn test_subtrees() {
+        let map = load("test1.in");
+        let name = |id: usize| map.name(id).to_string();
+        assert_eq!(map.roots().into_iter().map(name).collect::<Vec<String>>(), vec!["COM"]);
+        assert_eq!(map.subtree_size(map.id("COM").unwrap()), 14);
+        assert_eq!(map.subtree_size(map.id("E").unwrap()), 6);
+        assert_eq!(map.children(map.id("D").unwrap()).iter().map(|id| name(*id)).collect::<Vec<String>>(), vec!["E", "I"]);
+        let path = map.path(map.id("YOU").unwrap(), map.id("SAN").unwrap()).unwrap();
+        assert_eq!(path.into_iter().map(name).collect::<Vec<String>>(), vec!["YOU", "K", "J", "E", "D", "I", "SAN"]);
+    }
+
+    #[test]
+    fn test_separate_trees() {
+        let map = OrbitMap::from_routes(&[("A", "B"), ("B", "C"), ("X", "Y")]).unwrap();
+        assert_eq!(map.common_ancestor(map.id("C").unwrap(), map.id("Y").unwrap()), None);
+        assert_eq!(map.distance(map.id("C").unwrap(), map.id("A").unwrap()), Some(2));
+    }
+
+    #[test]
+    fn test_invalid_routes() {
+        let errors = OrbitMap::from_routes(&[("A", "B"), ("B", "C"), ("C", "A")]).err().unwrap();
+        assert_eq!(errors.len(), 1);
+        assert_eq!(errors[0].to_string(), "lines 1, 2, 3: orbit cycle B)C)A)B");
+        let errors = OrbitMap::from_routes(&[("COM", "A"), ("X", "A")]).err().unwrap();
+        assert_eq!(errors[0].to_string(), "line 2: A cannot orbit X, it already orbits COM (line 1)");
+    }
+}
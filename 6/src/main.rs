@@ -0,0 +1,76 @@
+use std::env;
+use std::fs;
+use std::process;
+
+use universal_orbit_map::export::{to_dot, to_tree};
+use universal_orbit_map::OrbitMap;
+
+fn main() {
+    let args:Vec<String> = env::args().collect();
+    let syntax = format!("Syntax : {} [--input <file>] [--root <object>] [--dot <file>] [--tree] [--collapse <size>] [<object> <object>]", args[0]);
+    let mut input_filename = "input.txt".to_string();
+    let mut root: Option<String> = None;
+    let mut names = Vec::<String>::new();
+    let mut dot_filename: Option<String> = None;
+    let mut tree = false;
+    let mut collapse_above: Option<usize> = None;
+    let mut it = args.iter().skip(1);
+    while let Some(arg) = it.next() {
+        match arg.as_str() {
+            "--input" => input_filename = it.next().expect("Missing input file name").clone(),
+            "--root" => root = Some(it.next().expect("Missing root name").clone()),
+            "--dot" => dot_filename = Some(it.next().expect("Missing DOT file name").clone()),
+            "--tree" => tree = true,
+            "--collapse" => collapse_above = Some(it.next().and_then(|size| size.parse().ok()).expect("Invalid subtree size")),
+            _ if !arg.starts_with("--") => names.push(arg.clone()),
+            _ => panic!("Unknown option {}\n{}", arg, syntax)
+        }
+    }
+    if names.is_empty() {
+        names = vec!["YOU".to_string(), "SAN".to_string()];
+    }
+    if names.len() != 2 {
+        panic!("{}", syntax);
+    }
+
+    let content = fs::read_to_string(&input_filename)
+        .unwrap_or_else(|_| panic!("Something went wrong reading {}", input_filename));
+    let map = match OrbitMap::parse(&content, root.as_deref()) {
+        Ok(map) => map,
+        Err(errors) => {
+            for err in errors {
+                eprintln!("{}: {}", input_filename, err);
+            }
+            process::exit(1);
+        }
+    };
+    println!("Orbits: {}", map.total_orbits());
+
+    let ids: Vec<usize> = names.iter()
+        .map(|name| map.id(name).unwrap_or_else(|| panic!("Unknown object {}", name)))
+        .collect();
+    match map.common_ancestor(ids[0], ids[1]) {
+        Some(ancestor) => {
+            println!("Common ancestor of {} and {}: {}", names[0], names[1], map.name(ancestor));
+            println!("Distance: {}", map.distance(ids[0], ids[1]).unwrap());
+        },
+        None => println!("{} and {} are not in the same orbit tree", names[0], names[1])
+    }
+    match map.transfers(ids[0], ids[1]) {
+        None => {
+            println!("No path found!");
+        },
+        Some(count) => {
+            println!("Minimum number of orbital transfers: {}", count);
+        }
+    }
+
+    let path = Some((ids[0], ids[1]));
+    if tree {
+        print!("{}", to_tree(&map, path, collapse_above));
+    }
+    if let Some(dot_filename) = dot_filename {
+        fs::write(&dot_filename, to_dot(&map, path, collapse_above))
+            .unwrap_or_else(|_| panic!("Something went wrong writing {}", dot_filename));
+    }
+}
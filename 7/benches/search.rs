@@ -0,0 +1,22 @@
+use criterion::{criterion_group, criterion_main, Criterion};
+
+use amplification_circuit::intcode_computer::Processor;
+use amplification_circuit::{search, AmplifierChain, Mode, Progress};
+
+const SERIAL_PROGRAM: &str = "3,31,3,32,1002,32,10,32,1001,31,-2,31,1007,31,0,33,1002,33,7,33,1,33,31,31,1,32,31,31,4,31,99,0,0,0";
+const FEEDBACK_PROGRAM: &str = "3,52,1001,52,-5,52,3,53,1,52,56,54,1007,54,5,55,1005,55,26,1001,54,-5,54,1105,1,12,1,53,54,53,\
+                                1008,54,0,55,1001,55,1,55,2,53,55,53,4,53,1001,56,-1,56,1005,56,6,99,0,0,0,0,10";
+
+fn bench_search(c: &mut Criterion) {
+    let serial = AmplifierChain::new(SERIAL_PROGRAM.parse::<Processor>().unwrap(), Mode::Serial, 0);
+    c.bench_function("serial search", |b| b.iter(|| {
+        search(&serial, &[0, 1, 2, 3, 4], 5, false, 1, &Progress::new(120))
+    }));
+    let feedback = AmplifierChain::new(FEEDBACK_PROGRAM.parse::<Processor>().unwrap(), Mode::Feedback, 0);
+    c.bench_function("feedback search", |b| b.iter(|| {
+        search(&feedback, &[5, 6, 7, 8, 9], 5, false, 1, &Progress::new(120))
+    }));
+}
+
+criterion_group!(benches, bench_search);
+criterion_main!(benches);
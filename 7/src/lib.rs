@@ -0,0 +1,343 @@
+use std::cmp::{Ordering, Reverse};
+use std::collections::BinaryHeap;
+use std::sync::atomic::{AtomicU64, Ordering as AtomicOrdering};
+
+use rayon::prelude::*;
+
+pub mod intcode_computer;
+
+use intcode_computer::Processor;
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Mode {
+    // each amplifier runs once, the last output is the thruster signal
+    Serial,
+    // the last amplifier feeds the first one until an amplifier halts
+    Feedback
+}
+
+pub struct AmplifierChain {
+    processor: Processor,
+    mode: Mode,
+    initial_signal: i64
+}
+
+impl AmplifierChain {
+    pub fn new(processor: Processor, mode: Mode, initial_signal: i64) -> AmplifierChain {
+        AmplifierChain {
+            processor,
+            mode,
+            initial_signal
+        }
+    }
+
+    pub fn run(&self, phases: &[i64]) -> i64 {
+        self.run_with(phases, &mut Vec::new())
+    }
+
+    // Runs the chain on processors, which are reset from the loaded program
+    // instead of being cloned for every phase setting.
+    pub fn run_with(&self, phases: &[i64], processors: &mut Vec<Processor>) -> i64 {
+        processors.truncate(phases.len());
+        for processor in processors.iter_mut() {
+            processor.reset_from(&self.processor);
+        }
+        while processors.len() < phases.len() {
+            processors.push(self.processor.clone());
+        }
+        let mut input = self.initial_signal;
+        for (processor, phase) in processors.iter_mut().zip(phases) {
+            match processor.process(&vec![phase, &input]) {
+                Some(val) => { input = val; },
+                None => { return input }
+            }
+        }
+        if self.mode == Mode::Serial {
+            return input;
+        }
+        loop {
+            for processor in processors.iter_mut() {
+                match processor.process(&vec![&input]) {
+                    Some(val) => { input = val; },
+                    None => { return input }
+                }
+            }
+        }
+    }
+}
+
+// Calls f with every assignment of phases to amplifiers, in lexicographic
+// order of the indexes in phases. Without repetition, each phase is used at
+// most once.
+pub fn for_each_phase_setting<F>(phases: &[i64], amplifiers: usize, repetition: bool, mut f: F)
+    where F: FnMut(&[i64]) {
+    if !repetition && amplifiers > phases.len() {
+        return;
+    }
+    let mut setting = Vec::<i64>::with_capacity(amplifiers);
+    let mut used = vec![false; phases.len()];
+    fill_phase_setting(phases, amplifiers, repetition, &mut setting, &mut used, &mut f);
+}
+
+pub fn count_phase_settings(phase_count: usize, amplifiers: usize, repetition: bool) -> u64 {
+    if repetition {
+        (0..amplifiers).fold(1u64, |count, _| count.saturating_mul(phase_count as u64))
+    } else if amplifiers > phase_count {
+        0
+    } else {
+        (0..amplifiers).fold(1u64, |count, index| count.saturating_mul((phase_count - index) as u64))
+    }
+}
+
+// Indexes of the first phases of the settings, used to split the search in
+// parallel tasks.
+fn phase_prefixes(phase_count: usize, length: usize, repetition: bool) -> Vec<Vec<usize>> {
+    let mut prefixes: Vec<Vec<usize>> = vec![vec![]];
+    for _ in 0..length {
+        prefixes = prefixes.iter().flat_map(|prefix| {
+            (0..phase_count)
+                .filter(move |index| repetition || !prefix.contains(index))
+                .map(move |index| {
+                    let mut longer = prefix.clone();
+                    longer.push(index);
+                    longer
+                })
+        }).collect();
+    }
+    prefixes
+}
+
+fn fill_phase_setting<F>(phases: &[i64], amplifiers: usize, repetition: bool, setting: &mut Vec<i64>, used: &mut Vec<bool>, f: &mut F)
+    where F: FnMut(&[i64]) {
+    if setting.len() == amplifiers {
+        f(setting);
+        return;
+    }
+    for (index, phase) in phases.iter().enumerate() {
+        if used[index] {
+            continue;
+        }
+        used[index] = !repetition;
+        setting.push(*phase);
+        fill_phase_setting(phases, amplifiers, repetition, setting, used, f);
+        setting.pop();
+        used[index] = false;
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct RankedSetting {
+    pub signal: i64,
+    pub phases: Vec<i64>
+}
+
+// A better setting has a higher signal, ties go to the smallest phases.
+impl Ord for RankedSetting {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.signal.cmp(&other.signal).then_with(|| other.phases.cmp(&self.phases))
+    }
+}
+
+impl PartialOrd for RankedSetting {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+pub struct TopSettings {
+    capacity: usize,
+    heap: BinaryHeap<Reverse<RankedSetting>>
+}
+
+impl TopSettings {
+    pub fn new(capacity: usize) -> TopSettings {
+        TopSettings {
+            capacity,
+            heap: BinaryHeap::new()
+        }
+    }
+
+    pub fn offer(&mut self, signal: i64, phases: &[i64]) {
+        if self.heap.len() == self.capacity {
+            // keep the new setting only if it ranks better than the worst kept one
+            match self.heap.peek() {
+                Some(Reverse(worst)) if (signal, worst.phases.as_slice()) > (worst.signal, phases) => {},
+                _ => return
+            }
+        }
+        self.push(RankedSetting { signal, phases: phases.to_vec() });
+    }
+
+    fn push(&mut self, ranked: RankedSetting) {
+        self.heap.push(Reverse(ranked));
+        if self.heap.len() > self.capacity {
+            self.heap.pop();
+        }
+    }
+
+    pub fn merge(mut self, other: TopSettings) -> TopSettings {
+        for Reverse(ranked) in other.heap {
+            self.push(ranked);
+        }
+        self
+    }
+
+    // best first
+    pub fn into_sorted_vec(self) -> Vec<RankedSetting> {
+        self.heap.into_sorted_vec().into_iter().map(|Reverse(ranked)| ranked).collect()
+    }
+}
+
+pub struct Progress {
+    done: AtomicU64,
+    total: u64
+}
+
+impl Progress {
+    pub fn new(total: u64) -> Progress {
+        Progress {
+            done: AtomicU64::new(0),
+            total
+        }
+    }
+
+    pub fn done(&self) -> u64 {
+        self.done.load(AtomicOrdering::Relaxed)
+    }
+
+    pub fn total(&self) -> u64 {
+        self.total
+    }
+
+    fn advance(&self, count: u64) {
+        self.done.fetch_add(count, AtomicOrdering::Relaxed);
+    }
+}
+
+// Settings are searched in parallel, one task per pair of first phases. As
+// RankedSetting is a total order, the result does not depend on scheduling.
+pub fn search(chain: &AmplifierChain, phases: &[i64], amplifiers: usize, repetition: bool, top: usize, progress: &Progress) -> Vec<RankedSetting> {
+    if !repetition && amplifiers > phases.len() {
+        return vec![];
+    }
+    phase_prefixes(phases.len(), usize::min(2, amplifiers), repetition)
+        .into_par_iter()
+        .map(|prefix| {
+            let mut best = TopSettings::new(top);
+            let mut processors = Vec::<Processor>::new();
+            let mut setting: Vec<i64> = prefix.iter().map(|index| phases[*index]).collect();
+            let mut used = vec![false; phases.len()];
+            if !repetition {
+                for index in &prefix {
+                    used[*index] = true;
+                }
+            }
+            let mut searched = 0;
+            fill_phase_setting(phases, amplifiers, repetition, &mut setting, &mut used, &mut |setting: &[i64]| {
+                best.offer(chain.run_with(setting, &mut processors), setting);
+                searched += 1;
+                if searched % 1024 == 0 {
+                    progress.advance(1024);
+                }
+            });
+            progress.advance(searched % 1024);
+            best
+        })
+        .reduce(|| TopSettings::new(top), TopSettings::merge)
+        .into_sorted_vec()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn check(program: &str, mode: Mode, signal: i64, phases: &[i64]) {
+        let chain = AmplifierChain::new(program.parse::<Processor>().unwrap(), mode, 0);
+        assert_eq!(chain.run(phases), signal);
+        let base_phases = if mode == Mode::Serial { [0, 1, 2, 3, 4] } else { [5, 6, 7, 8, 9] };
+        let best = search(&chain, &base_phases, 5, false, 1, &Progress::new(120));
+        assert_eq!(best, [RankedSetting { signal, phases: phases.to_vec() }]);
+    }
+
+    #[test]
+    fn test_serial1() {
+        check("3,15,3,16,1002,16,10,16,1,16,15,15,4,15,99,0,0", Mode::Serial, 43210, &[4, 3, 2, 1, 0]);
+    }
+
+    #[test]
+    fn test_serial2() {
+        check("3,23,3,24,1002,24,10,24,1002,23,-1,23,101,5,23,23,1,24,23,23,4,23,99,0,0",
+              Mode::Serial, 54321, &[0, 1, 2, 3, 4]);
+    }
+
+    #[test]
+    fn test_serial3() {
+        check("3,31,3,32,1002,32,10,32,1001,31,-2,31,1007,31,0,33,1002,33,7,33,1,33,31,31,1,32,31,31,4,31,99,0,0,0",
+              Mode::Serial, 65210, &[1, 0, 4, 3, 2]);
+    }
+
+    #[test]
+    fn test_feedback1() {
+        check("3,26,1001,26,-4,26,3,27,1002,27,2,27,1,27,26,27,4,27,1001,28,-1,28,1005,28,6,99,0,0,5",
+              Mode::Feedback, 139629729, &[9, 8, 7, 6, 5]);
+    }
+
+    #[test]
+    fn test_feedback2() {
+        check("3,52,1001,52,-5,52,3,53,1,52,56,54,1007,54,5,55,1005,55,26,1001,54,-5,54,1105,1,12,1,53,54,53,\
+               1008,54,0,55,1001,55,1,55,2,53,55,53,4,53,1001,56,-1,56,1005,56,6,99,0,0,0,0,10",
+              Mode::Feedback, 18216, &[9, 7, 8, 5, 6]);
+    }
+
+    #[test]
+    fn test_phase_settings() {
+        let mut settings = Vec::<Vec<i64>>::new();
+        for_each_phase_setting(&[1, 2, 3], 2, false, |setting| settings.push(setting.to_vec()));
+        assert_eq!(settings, [[1, 2], [1, 3], [2, 1], [2, 3], [3, 1], [3, 2]]);
+        assert_eq!(count_phase_settings(3, 2, false), 6);
+        assert_eq!(count_phase_settings(3, 2, true), 9);
+        assert_eq!(count_phase_settings(2, 3, false), 0);
+        assert_eq!(count_phase_settings(10, 30, true), u64::MAX);
+        settings.clear();
+        for_each_phase_setting(&[1, 2], 2, true, |setting| settings.push(setting.to_vec()));
+        assert_eq!(settings, [[1, 1], [1, 2], [2, 1], [2, 2]]);
+        settings.clear();
+        for_each_phase_setting(&[1, 2], 3, false, |setting| settings.push(setting.to_vec()));
+        assert!(settings.is_empty());
+    }
+
+    #[test]
+    fn test_parallel_search() {
+        let chain = AmplifierChain::new("3,15,3,16,1002,16,10,16,1,16,15,15,4,15,99,0,0".parse::<Processor>().unwrap(), Mode::Serial, 0);
+        let mut expected = Vec::<RankedSetting>::new();
+        let mut processors = Vec::<Processor>::new();
+        for_each_phase_setting(&[0, 1, 2], 4, true, |setting| {
+            let signal = chain.run_with(setting, &mut processors);
+            assert_eq!(signal, chain.run(setting));
+            expected.push(RankedSetting { signal, phases: setting.to_vec() });
+        });
+        expected.sort_by(|a, b| b.cmp(a));
+        expected.truncate(10);
+        let progress = Progress::new(count_phase_settings(3, 4, true));
+        assert_eq!(search(&chain, &[0, 1, 2], 4, true, 10, &progress), expected);
+        assert_eq!((progress.done(), progress.total()), (81, 81));
+    }
+
+    #[test]
+    fn test_top_settings() {
+        let chain = AmplifierChain::new("3,15,3,16,1002,16,10,16,1,16,15,15,4,15,99,0,0".parse::<Processor>().unwrap(), Mode::Serial, 0);
+        let progress = Progress::new(count_phase_settings(3, 2, true));
+        let best = search(&chain, &[0, 1, 2], 2, true, 2, &progress);
+        assert_eq!(best, [RankedSetting { signal: 22, phases: vec![2, 2] }, RankedSetting { signal: 21, phases: vec![2, 1] }]);
+        assert_eq!(progress.done(), 9);
+        let mut top = TopSettings::new(2);
+        top.offer(5, &[1, 2]);
+        top.offer(7, &[2, 1]);
+        top.offer(7, &[0, 3]);
+        top.offer(6, &[3, 0]);
+        assert_eq!(top.into_sorted_vec(), [
+            RankedSetting { signal: 7, phases: vec![0, 3] },
+            RankedSetting { signal: 7, phases: vec![2, 1] }
+        ]);
+    }
+}
@@ -0,0 +1,108 @@
+use std::env;
+use std::io::{stderr, Write};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+use std::thread;
+use std::time::Duration;
+
+use amplification_circuit::intcode_computer::Processor;
+use amplification_circuit::{count_phase_settings, search, AmplifierChain, Mode, Progress, RankedSetting};
+
+struct SearchOptions {
+    phases: Option<Vec<i64>>,
+    amplifiers: Option<usize>,
+    initial_signal: i64,
+    repetition: bool,
+    top: usize,
+    quiet: bool
+}
+
+fn parse_phases(arg: &str) -> Vec<i64> {
+    arg.split(',').map(|p| p.trim().parse::<i64>().expect("Invalid phase")).collect()
+}
+
+fn display_progress(progress: &Progress) {
+    let percent = if progress.total() == 0 { 100 } else { progress.done() * 100 / progress.total() };
+    eprint!("\rSearched {}/{} phase settings ({}%)", progress.done(), progress.total(), percent);
+    stderr().flush().unwrap();
+}
+
+fn run_search(chain: &AmplifierChain, phases: &[i64], amplifiers: usize, options: &SearchOptions) -> Vec<RankedSetting> {
+    let progress = Arc::new(Progress::new(count_phase_settings(phases.len(), amplifiers, options.repetition)));
+    let finished = Arc::new(AtomicBool::new(false));
+    let progress_display = if options.quiet {
+        None
+    } else {
+        let progress = Arc::clone(&progress);
+        let finished = Arc::clone(&finished);
+        Some(thread::spawn(move || {
+            while !finished.load(Ordering::Relaxed) {
+                display_progress(&progress);
+                thread::sleep(Duration::from_millis(200));
+            }
+            display_progress(&progress);
+            eprintln!();
+        }))
+    };
+    let ranking = search(chain, phases, amplifiers, options.repetition, options.top, &progress);
+    finished.store(true, Ordering::Relaxed);
+    if let Some(handle) = progress_display {
+        handle.join().unwrap();
+    }
+    ranking
+}
+
+fn main() {
+    let args:Vec<String> = env::args().collect();
+    let mut program_filename = "input.txt".to_string();
+    let mut modes = vec![Mode::Serial, Mode::Feedback];
+    let mut options = SearchOptions {
+        phases: None,
+        amplifiers: None,
+        initial_signal: 0,
+        repetition: false,
+        top: 1,
+        quiet: false
+    };
+    let mut it = args.iter().skip(1);
+    while let Some(arg) = it.next() {
+        let mut value = || it.next().unwrap_or_else(|| panic!("Missing value for {}", arg)).as_str();
+        match arg.as_str() {
+            "--program" => program_filename = value().to_string(),
+            "--phases" => options.phases = Some(parse_phases(value())),
+            "--amplifiers" => options.amplifiers = Some(value().parse::<usize>().expect("Invalid amplifier count")),
+            "--mode" => {
+                modes = match value() {
+                    "serial" => vec![Mode::Serial],
+                    "feedback" => vec![Mode::Feedback],
+                    _ => panic!("Mode must be serial or feedback")
+                }
+            },
+            "--signal" => options.initial_signal = value().parse::<i64>().expect("Invalid initial signal"),
+            "--repetition" => options.repetition = true,
+            "--top" => options.top = value().parse::<usize>().expect("Invalid top count"),
+            "--quiet" => options.quiet = true,
+            _ => panic!("Syntax : {} [--program <file>] [--phases 5,6,7,8,9] [--amplifiers <count>] [--mode serial|feedback] \
+                        [--signal <initial signal>] [--repetition] [--top <count>] [--quiet]", args[0])
+        }
+    }
+
+    let processor = Processor::init(&program_filename);
+    for mode in modes {
+        let phases = match &options.phases {
+            Some(phases) => phases.clone(),
+            None if mode == Mode::Serial => vec![0, 1, 2, 3, 4],
+            None => vec![5, 6, 7, 8, 9]
+        };
+        let amplifiers = options.amplifiers.unwrap_or(phases.len());
+        let chain = AmplifierChain::new(processor.clone(), mode, options.initial_signal);
+        println!("{:?} mode:", mode);
+        let ranking = run_search(&chain, &phases, amplifiers, &options);
+        if ranking.is_empty() {
+            println!("No phase setting for {} amplifiers from phases {:?}", amplifiers, phases);
+        }
+        for (rank, ranked) in ranking.iter().enumerate() {
+            println!("{}. {} for phases {:?}", rank + 1, ranked.signal, ranked.phases);
+        }
+    }
+}
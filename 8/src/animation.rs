@@ -0,0 +1,96 @@
+use std::io::Write;
+use std::thread;
+use std::time::Duration;
+
+use gif::SetParameter;
+use image::{DynamicImage, GenericImageView, RgbaImage};
+
+use crate::palette::Palette;
+use crate::{Layer, SifError};
+
+fn io_error(err: std::io::Error) -> SifError {
+    SifError::Io(err.to_string())
+}
+
+// Animated GIF with one frame per composite, looping forever.
+pub fn write_gif<W: Write>(w: W, frames: &[Layer], palette: &Palette, delay: Duration) -> Result<(), SifError> {
+    let first = frames.first().ok_or(SifError::Empty)?;
+    if first.cols() > u32::from(u16::MAX) || first.rows() > u32::from(u16::MAX) {
+        return Err(SifError::InvalidDimensions { rows: first.rows(), cols: first.cols() });
+    }
+    let (width, height) = (first.cols() as u16, first.rows() as u16);
+    let colors: Vec<u8> = palette.colors().iter().flat_map(|color| color[..3].to_vec()).collect();
+    let transparent = palette.colors().iter().position(|color| color[3] == 0).map(|digit| digit as u8);
+    let mut encoder = gif::Encoder::new(w, width, height, &colors).map_err(io_error)?;
+    encoder.set(gif::Repeat::Infinite).map_err(io_error)?;
+    for layer in frames {
+        if let Some(digit) = layer.buffer.iter().find(|digit| palette.color(**digit).is_none()) {
+            return Err(SifError::MissingColor { digit: *digit });
+        }
+        let mut frame = gif::Frame::from_indexed_pixels(width, height, &layer.buffer, transparent);
+        frame.delay = (delay.as_millis() / 10).min(u128::from(u16::MAX)) as u16;
+        encoder.write_frame(&frame).map_err(io_error)?;
+    }
+    Ok(())
+}
+
+// termimage draws two pixel rows per text line, so an odd height gets an
+// extra transparent row.
+pub(crate) fn to_image(layer: &Layer, palette: &Palette) -> Result<DynamicImage, SifError> {
+    let rows = layer.rows() + layer.rows() % 2;
+    let mut rgba = layer.to_rgba(palette)?;
+    rgba.resize((rows * layer.cols() * 4) as usize, 0);
+    Ok(DynamicImage::ImageRgba8(RgbaImage::from_raw(layer.cols(), rows, rgba).unwrap()))
+}
+
+// Draws the frames over each other, waiting delay after each one.
+pub fn play<W: Write>(w: &mut W, frames: &[Layer], palette: &Palette, delay: Duration) -> Result<(), SifError> {
+    for (index, layer) in frames.iter().enumerate() {
+        let img = to_image(layer, palette)?;
+        if index > 0 {
+            write!(w, "\x1b[{}A", img.height() / 2).map_err(io_error)?;
+        }
+        termimage::ops::write_ansi_truecolor(w, &img);
+        w.flush().map_err(io_error)?;
+        thread::sleep(delay);
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Image;
+
+    #[test]
+    fn test_gif_animation() {
+        let image = Image::decode("0222112222120000", 2, 2).unwrap();
+        let frames = image.compositions();
+        assert_eq!(frames.iter().map(|layer| layer.to_string()).collect::<Vec<String>>(),
+                   vec!["02\n22\n", "01\n22\n", "01\n12\n", "01\n10\n"]);
+        let mut gif = Vec::<u8>::new();
+        write_gif(&mut gif, &frames, &Palette::default(), Duration::from_millis(250)).unwrap();
+
+        let mut decoder = gif::Decoder::new(&gif[..]);
+        gif::SetParameter::set(&mut decoder, gif::ColorOutput::Indexed);
+        let mut reader = decoder.read_info().unwrap();
+        let mut decoded = Vec::<Vec<u8>>::new();
+        while let Some(frame) = reader.read_next_frame().unwrap() {
+            assert_eq!(frame.delay, 25);
+            assert_eq!(frame.transparent, Some(2));
+            decoded.push(frame.buffer.to_vec());
+        }
+        assert_eq!(decoded, vec![vec![0, 2, 2, 2], vec![0, 1, 2, 2], vec![0, 1, 1, 2], vec![0, 1, 1, 0]]);
+    }
+
+    #[test]
+    fn test_to_image() {
+        let layer = Image::decode("012", 3, 1).unwrap().flatten();
+        let img = to_image(&layer, &Palette::default()).unwrap().to_rgba();
+        assert_eq!(img.dimensions(), (3, 2));
+        assert_eq!(img.into_raw(), vec![0, 0, 0, 255, 255, 255, 255, 255, 0, 0, 0, 0,
+                                         0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0]);
+        let layer = Image::decode("3", 1, 1).unwrap().flatten();
+        assert_eq!(to_image(&layer, &Palette::default()).err(), Some(SifError::MissingColor { digit: 3 }));
+    }
+}
@@ -0,0 +1,93 @@
+use crate::{Image, Layer, SifError};
+
+// Text bitmap: one line per row, 0 or . for black, 1 or # for white, 2 for
+// transparent and other digits for further palette colors. Trailing whitespace
+// is ignored.
+pub fn parse_bitmap(content: &str) -> Result<Layer, SifError> {
+    let lines: Vec<&str> = content.trim_end().lines().map(str::trim_end).collect();
+    let cols = lines.first().map_or(0, |line| line.chars().count());
+    if cols == 0 {
+        return Err(SifError::Empty);
+    }
+    let mut buffer = Vec::<u8>::with_capacity(cols * lines.len());
+    let mut position = 0;
+    for (index, line) in lines.iter().enumerate() {
+        if line.chars().count() != cols {
+            return Err(SifError::RaggedBitmap { line: index + 1 });
+        }
+        for character in line.chars() {
+            buffer.push(match character {
+                '0' | '.' => 0,
+                '1' | '#' => 1,
+                '2'..='9' => character as u8 - b'0',
+                _ => return Err(SifError::InvalidDigit { position, character })
+            });
+            position += 1;
+        }
+        position += 1;
+    }
+    Ok(Layer::from_pixels(buffer, lines.len() as u32, cols as u32))
+}
+
+// Spreads layer over layer_count layers: each pixel is transparent in the
+// layers above the one revealing it, and replaced by another opaque digit in
+// the layers below so that only composition gives the right picture back.
+// Transparent pixels stay transparent in every layer.
+pub fn encode(layer: &Layer, layer_count: u32) -> Result<Image, SifError> {
+    if layer_count == 0 {
+        return Err(SifError::InvalidLayerCount);
+    }
+    let layer_size = layer.layer_size();
+    let layers = (0..layer_count).map(|layer_index| {
+        let buffer = (0..layer_size).map(|pixel_index| {
+            let pixel = layer.get_pixel(pixel_index / layer.cols(), pixel_index % layer.cols());
+            let reveal = pixel_index % layer_count;
+            if pixel == 2 || layer_index < reveal {
+                2
+            } else if layer_index == reveal {
+                pixel
+            } else if pixel == 0 {
+                1
+            } else {
+                0
+            }
+        }).collect();
+        Layer::from_pixels(buffer, layer.rows(), layer.cols())
+    }).collect();
+    Image::from_layers(layers)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::palette::Palette;
+
+    #[test]
+    fn test_encode() {
+        let layer = parse_bitmap("#..#\n.##2\n").unwrap();
+        assert_eq!(layer.to_string(), "1001\n0112\n");
+        let image = encode(&layer, 3).unwrap();
+        assert_eq!(image.to_sif(), "122122120020020201001102");
+        let decoded = Image::decode(&image.to_sif(), 4, 2).unwrap();
+        assert_eq!(decoded.flatten(), layer);
+        assert_eq!(encode(&layer, 0), Err(SifError::InvalidLayerCount));
+        assert_eq!(parse_bitmap("#.\n#"), Err(SifError::RaggedBitmap { line: 2 }));
+        assert_eq!(parse_bitmap("#.\n#x"), Err(SifError::InvalidDigit { position: 4, character: 'x' }));
+    }
+
+    #[test]
+    fn test_four_colors() {
+        let palette = "000000,ffffff,00000000,ff0000".parse::<Palette>().unwrap();
+        let layer = parse_bitmap("#.3\n32#\n").unwrap();
+        assert_eq!(layer.to_string(), "103\n321\n");
+        let mut png = Vec::<u8>::new();
+        layer.write_png(&mut png, &palette).unwrap();
+        let read = Layer::read_png(&png[..], &palette).unwrap();
+        assert_eq!(read, layer);
+        let image = encode(&read, 4).unwrap();
+        assert_eq!(image.to_sif(), "122222002221013220010320");
+        let decoded = Image::decode(&image.to_sif(), 3, 2).unwrap();
+        assert_eq!(decoded, image);
+        assert_eq!(decoded.flatten(), layer);
+    }
+}
@@ -0,0 +1,303 @@
+use std::fmt;
+use std::fs::File;
+use std::io::{BufWriter, Read, Write};
+
+pub mod animation;
+pub mod encoder;
+pub mod ocr;
+pub mod palette;
+
+use palette::Palette;
+
+#[derive(Debug, PartialEq)]
+pub enum SifError {
+    InvalidDimensions { rows: u32, cols: u32 },
+    InvalidDigit { position: usize, character: char },
+    IncompleteLayer { length: usize, layer_size: usize },
+    Empty,
+    InvalidLayerCount,
+    RaggedBitmap { line: usize },
+    InvalidPalette(String),
+    MissingColor { digit: u8 },
+    UnknownColor { row: u32, col: u32, color: [u8; 4] },
+    Png(String),
+    Io(String)
+}
+
+impl fmt::Display for SifError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            SifError::InvalidDimensions { rows, cols } => write!(f, "invalid dimensions {}x{}", cols, rows),
+            SifError::InvalidDigit { position, character } => write!(f, "invalid pixel {:?} at position {}, expected a digit", character, position),
+            SifError::IncompleteLayer { length, layer_size } => write!(f, "{} pixels is not a multiple of the layer size {}", length, layer_size),
+            SifError::Empty => write!(f, "image has no layer"),
+            SifError::InvalidLayerCount => write!(f, "an image needs at least one layer"),
+            SifError::RaggedBitmap { line } => write!(f, "line {} does not have the same length as the first one", line),
+            SifError::InvalidPalette(message) => write!(f, "invalid palette: {}", message),
+            SifError::MissingColor { digit } => write!(f, "no color in palette for digit {}", digit),
+            SifError::UnknownColor { row, col, color } => write!(f, "color {:?} at row {}, column {} is not in palette", color, row, col),
+            SifError::Png(message) => write!(f, "PNG error: {}", message),
+            SifError::Io(message) => write!(f, "I/O error: {}", message)
+        }
+    }
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct Layer {
+    buffer: Vec<u8>,
+    rows: u32,
+    cols: u32
+}
+
+impl Layer {
+    fn create(src: &[u8], start: u32, rows: u32, cols: u32) -> Layer {
+        let mut dst: Vec<u8> = vec![0 ;Layer::layer_size_static(rows,cols) as usize];
+        let end = start+rows*cols;
+        dst.clone_from_slice(&src[(start as usize)..(end as usize)]);
+        Layer {
+            buffer: dst,
+            rows,
+            cols
+        }
+    }
+
+    pub fn from_pixels(buffer: Vec<u8>, rows: u32, cols: u32) -> Layer {
+        assert_eq!(buffer.len(), Layer::layer_size_static(rows, cols) as usize);
+        Layer {
+            buffer,
+            rows,
+            cols
+        }
+    }
+
+    fn layer_size_static(rows: u32, cols: u32) -> u32 {
+       rows*cols
+    }
+
+    pub fn layer_size(&self) -> u32 {
+        Layer::layer_size_static(self.rows, self.cols)
+    }
+
+    pub fn rows(&self) -> u32 {
+        self.rows
+    }
+
+    pub fn cols(&self) -> u32 {
+        self.cols
+    }
+
+    pub fn count_digit(&self, digit: u8) -> u32 {
+        self.buffer.iter().map(|b| if *b == digit { 1 } else { 0 }).sum()
+    }
+
+    pub fn compose(&self, other: &Layer) -> Layer {
+        let composed: Vec<u8> = self.buffer.iter().zip(other.buffer.iter())
+            .map(|(first, second)| Layer::compose_pixels(*first,*second)).collect();
+        Layer {
+            buffer: composed,
+            rows: self.rows,
+            cols: self.cols
+        }
+    }
+
+    // 2 is transparent, any other digit is opaque
+    fn compose_pixels(first: u8, second: u8) -> u8 {
+        match first {
+            2 => second,
+            _ => first
+        }
+    }
+
+    pub fn get_pixel(&self, row: u32, col: u32) -> u8 {
+        self.buffer[(row*self.cols + col) as usize]
+    }
+
+    // Reads the letters drawn with white (1) pixels.
+    pub fn recognize(&self) -> Result<String, ocr::OcrError> {
+        let grid: Vec<Vec<bool>> = self.buffer.chunks(self.cols as usize)
+            .map(|row| row.iter().map(|pixel| *pixel == 1).collect())
+            .collect();
+        ocr::recognize_grid(&grid)
+    }
+
+    pub fn output_image(&self, filename: &str, palette: &Palette) -> Result<(), SifError> {
+        let file = File::create(filename).map_err(|err| SifError::Io(err.to_string()))?;
+        self.write_png(BufWriter::new(file), palette)
+    }
+
+    // RGBA bytes, row by row, as expected by the PNG encoder and by image.
+    pub fn to_rgba(&self, palette: &Palette) -> Result<Vec<u8>, SifError> {
+        Ok(self.buffer.iter().map(|digit| palette.color(*digit).ok_or(SifError::MissingColor { digit: *digit }))
+            .collect::<Result<Vec<[u8; 4]>, SifError>>()?.concat())
+    }
+
+    pub fn write_png<W: Write>(&self, w: W, palette: &Palette) -> Result<(), SifError> {
+        let rgba = self.to_rgba(palette)?;
+        let mut encoder = png::Encoder::new(w, self.cols, self.rows);
+        encoder.set_color(png::ColorType::RGBA);
+        encoder.set_depth(png::BitDepth::Eight);
+        let mut writer = encoder.write_header().map_err(|err| SifError::Png(err.to_string()))?;
+        writer.write_image_data(&rgba).map_err(|err| SifError::Png(err.to_string()))
+    }
+
+    // Any PNG color type is accepted, as long as every pixel color is in palette.
+    pub fn read_png<R: Read>(r: R, palette: &Palette) -> Result<Layer, SifError> {
+        let mut decoder = png::Decoder::new(r);
+        decoder.set_transformations(png::Transformations::EXPAND | png::Transformations::STRIP_16);
+        let (info, mut reader) = decoder.read_info().map_err(|err| SifError::Png(err.to_string()))?;
+        let mut data = vec![0; info.buffer_size()];
+        reader.next_frame(&mut data).map_err(|err| SifError::Png(err.to_string()))?;
+        let channels = match info.color_type {
+            png::ColorType::Grayscale => 1,
+            png::ColorType::GrayscaleAlpha => 2,
+            png::ColorType::RGB => 3,
+            png::ColorType::RGBA => 4,
+            png::ColorType::Indexed => return Err(SifError::Png("indexed colors were not expanded".to_string()))
+        };
+        let mut buffer = Vec::<u8>::with_capacity((info.width*info.height) as usize);
+        for row in 0..info.height {
+            let line = &data[row as usize*info.line_size..];
+            for col in 0..info.width {
+                let pixel = &line[col as usize*channels..(col as usize + 1)*channels];
+                let color = match pixel {
+                    [gray] => [*gray, *gray, *gray, 255],
+                    [gray, alpha] => [*gray, *gray, *gray, *alpha],
+                    [r, g, b] => [*r, *g, *b, 255],
+                    _ => [pixel[0], pixel[1], pixel[2], pixel[3]]
+                };
+                buffer.push(palette.digit(color).ok_or(SifError::UnknownColor { row, col, color })?);
+            }
+        }
+        Ok(Layer::from_pixels(buffer, info.height, info.width))
+    }
+}
+
+impl fmt::Display for Layer {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let mut res = String::new();
+        for row in 0..self.rows {
+            for col in 0..self.cols {
+                res = res + &format!("{}", self.get_pixel(row,col));
+            }
+            res += "\n";
+        }
+        write!(f, "{}", res)
+    }
+}
+
+// Never empty: decode and from_layers reject images without layers.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Image {
+    layers: Vec<Layer>
+}
+
+impl Image {
+    pub fn from_layers(layers: Vec<Layer>) -> Result<Image, SifError> {
+        if layers.is_empty() {
+            return Err(SifError::Empty);
+        }
+        Ok(Image { layers })
+    }
+
+    // Trailing whitespace is ignored, any other character than a digit is
+    // rejected.
+    pub fn decode(content: &str, cols: u32, rows: u32) -> Result<Image, SifError> {
+        if rows == 0 || cols == 0 {
+            return Err(SifError::InvalidDimensions { rows, cols });
+        }
+        let content: Vec<u8> = content.trim_end().chars().enumerate().map(|(position, character)| {
+            match character {
+                '0'..='9' => Ok(character as u8 - b'0'),
+                _ => Err(SifError::InvalidDigit { position, character })
+            }
+        }).collect::<Result<Vec<u8>, SifError>>()?;
+        let layer_size = Layer::layer_size_static(rows, cols);
+        if content.is_empty() {
+            return Err(SifError::Empty);
+        }
+        if content.len() % layer_size as usize != 0 {
+            return Err(SifError::IncompleteLayer { length: content.len(), layer_size: layer_size as usize });
+        }
+        let layers = (0..content.len() as u32).step_by(layer_size as usize)
+            .map(|start| Layer::create(&content, start, rows, cols))
+            .collect();
+        Ok(Image { layers })
+    }
+
+    pub fn layers(&self) -> &[Layer] {
+        &self.layers
+    }
+
+    // Number of 1 digits multiplied by the number of 2 digits in the layer
+    // with the fewest 0 digits.
+    pub fn checksum(&self) -> u32 {
+        let layer = self.layers.iter().min_by_key(|layer| layer.count_digit(0)).unwrap();
+        layer.count_digit(1)*layer.count_digit(2)
+    }
+
+    pub fn to_sif(&self) -> String {
+        self.layers.iter().flat_map(|layer| layer.buffer.iter()).map(|digit| (b'0' + digit) as char).collect()
+    }
+
+    // The first layer, then the first two composed, and so on up to the
+    // flattened image.
+    pub fn compositions(&self) -> Vec<Layer> {
+        let mut compositions: Vec<Layer> = Vec::with_capacity(self.layers.len());
+        for layer in &self.layers {
+            let composed = match compositions.last() {
+                Some(previous) => previous.compose(layer),
+                None => layer.clone()
+            };
+            compositions.push(composed);
+        }
+        compositions
+    }
+
+    pub fn flatten(&self) -> Layer {
+        let mut it = self.layers.iter();
+        let mut cur_layer: Layer = it.next().unwrap().clone();
+        for next_layer in it {
+            cur_layer = cur_layer.compose(next_layer);
+        }
+        cur_layer
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_decode() {
+        let image = Image::decode("0222112222120000\n", 2, 2).unwrap();
+        assert_eq!(image.layers().len(), 4);
+        assert_eq!(image.layers()[1].to_string(), "11\n22\n");
+        assert_eq!(image.checksum(), 4);
+        assert_eq!(image.flatten().to_string(), "01\n10\n");
+    }
+
+    #[test]
+    fn test_invalid_images() {
+        assert_eq!(Image::decode("0122x", 2, 2), Err(SifError::InvalidDigit { position: 4, character: 'x' }));
+        assert_eq!(Image::decode("012201", 2, 2), Err(SifError::IncompleteLayer { length: 6, layer_size: 4 }));
+        assert_eq!(Image::decode("\n", 2, 2), Err(SifError::Empty));
+        assert_eq!(Image::decode("0122", 0, 2), Err(SifError::InvalidDimensions { rows: 2, cols: 0 }));
+        assert_eq!(Image::from_layers(vec![]), Err(SifError::Empty));
+    }
+
+    #[test]
+    fn test_png_round_trip() {
+        let layer = Image::decode("012210", 3, 2).unwrap().flatten();
+        let palettes = [Palette::default(), "102030,405060,708090a0".parse::<Palette>().unwrap()];
+        for palette in palettes.iter() {
+            let mut png = Vec::<u8>::new();
+            layer.write_png(&mut png, palette).unwrap();
+            assert_eq!(Layer::read_png(&png[..], palette).unwrap(), layer);
+        }
+        let mut png = Vec::<u8>::new();
+        layer.write_png(&mut png, &Palette::default()).unwrap();
+        let black_and_white = "000000,ffffff".parse::<Palette>().unwrap();
+        assert_eq!(Layer::read_png(&png[..], &black_and_white), Err(SifError::UnknownColor { row: 0, col: 2, color: [0, 0, 0, 0] }));
+        assert_eq!(layer.write_png(Vec::new(), &black_and_white), Err(SifError::MissingColor { digit: 2 }));
+    }
+}
@@ -1,123 +1,112 @@
+use std::env;
 use std::fs;
-use std::fmt;
 use std::fs::File;
-use std::io::BufWriter;
-
-#[derive(Clone)]
-struct Layer {
-    buffer: Vec<u8>,
-    rows: u32,
-    cols: u32
+use std::io::{stdout, BufReader, BufWriter};
+use std::process;
+use std::time::Duration;
+
+use sif::animation::{play, write_gif};
+use sif::encoder::{encode, parse_bitmap};
+use sif::palette::Palette;
+use sif::{Image, Layer, SifError};
+
+fn fail(filename: &str, err: SifError) -> ! {
+    eprintln!("{}: {}", filename, err);
+    process::exit(1);
 }
 
-impl Layer {
-    fn create(src: &Vec<u8>, start: u32, rows: u32, cols: u32) -> Layer {
-        let mut dst: Vec<u8> = vec![0 ;Layer::layer_size_static(rows,cols) as usize];
-        let end = start+rows*cols;
-        dst.clone_from_slice(&src[(start as usize)..(end as usize)]);
-        Layer {
-            buffer: dst,
-            rows,
-            cols
-        }
-    }
-
-    fn layer_size_static(rows: u32, cols: u32) -> u32 {
-       rows*cols
-    }
-
-    #[allow(dead_code)]
-    fn layer_size(&self) -> u32 {
-        Layer::layer_size_static(self.rows, self.cols)
-    }
+fn syntax(program: &str) -> ! {
+    panic!("Syntax : {} [decode] [--width <pixels>] [--height <pixels>] [--palette <colors>] [--output <png file>]\n\
+            \x20          [--gif <gif file>] [--play] [--delay <milliseconds>] [input file]\n\
+            \x20        {} encode [--layers <count>] [--palette <colors>] [--output <sif file>] <png or text bitmap file>", program, program)
+}
 
-    fn count_digit(&self, digit: u8) -> u32 {
-        self.buffer.iter().map(|b| if *b == digit { 1 } else { 0 }).sum()
-    }
+struct Animation {
+    gif_filename: Option<String>,
+    play: bool,
+    delay: Duration
+}
 
-    fn compose(&self, other: &Layer) -> Layer {
-        let composed: Vec<u8> = self.buffer.iter().zip(other.buffer.iter())
-            .map(|(first, second)| Layer::compose_pixels(*first,*second)).collect();
-        Layer {
-            buffer: composed,
-            rows: self.rows,
-            cols: self.cols
-        }
-    }
+fn decode(input_filename: &str, cols: u32, rows: u32, palette: &Palette, output_filename: &str, animation: &Animation) {
+    let content: String = fs::read_to_string(input_filename)
+        .unwrap_or_else(|_| panic!("Error reading {}", input_filename));
+    let image = Image::decode(&content, cols, rows).unwrap_or_else(|err| fail(input_filename, err));
 
-    fn compose_pixels(first: u8, second: u8) -> u8 {
-        match first {
-            0 => 0,
-            1 => 1,
-            _ => second
-        }
+    println!("{}", image.checksum());
+    let cur_layer = image.flatten();
+    println!("{}", cur_layer);
+    match cur_layer.recognize() {
+        Ok(text) => println!("{}", text),
+        Err(err) => eprintln!("Could not read the message: {}", err)
     }
+    cur_layer.output_image(output_filename, palette).unwrap_or_else(|err| fail(output_filename, err));
 
-    fn get_pixel(&self, row: u32, col: u32) -> u8 {
-        self.buffer[(row*self.cols + col) as usize]
+    if let Some(gif_filename) = &animation.gif_filename {
+        let file = File::create(gif_filename).unwrap_or_else(|_| panic!("Error writing {}", gif_filename));
+        write_gif(BufWriter::new(file), &image.compositions(), palette, animation.delay)
+            .unwrap_or_else(|err| fail(gif_filename, err));
     }
-
-    fn output_image(&self, filename: &str) {
-        let file = File::create(filename).unwrap();
-        let ref mut w = BufWriter::new(file);
-        let mut encoder = png::Encoder::new(w, self.cols, self.rows);
-        encoder.set_color(png::ColorType::Grayscale);
-        let mut writer = encoder.write_header().unwrap();
-        let vec_255: Vec<u8> = self.buffer.iter().map(|b| b*255).collect();
-        writer.write_image_data(&vec_255).unwrap();
+    if animation.play {
+        play(&mut stdout(), &image.compositions(), palette, animation.delay)
+            .unwrap_or_else(|err| fail(input_filename, err));
     }
 }
 
-impl fmt::Display for Layer {
-    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        let mut res = String::new();
-        for row in 0..self.rows {
-            for col in 0..self.cols {
-                res = res + &format!("{}", self.get_pixel(row,col));
-            }
-            res = res + "\n";
-        }
-        write!(f, "{}", res)
+fn encode_file(input_filename: &str, layer_count: u32, palette: &Palette, output_filename: Option<&str>) {
+    let layer = if input_filename.ends_with(".png") {
+        let file = File::open(input_filename).unwrap_or_else(|_| panic!("Error reading {}", input_filename));
+        Layer::read_png(BufReader::new(file), palette)
+    } else {
+        let content = fs::read_to_string(input_filename).unwrap_or_else(|_| panic!("Error reading {}", input_filename));
+        parse_bitmap(&content)
+    }.unwrap_or_else(|err| fail(input_filename, err));
+    let image = encode(&layer, layer_count).unwrap_or_else(|err| fail(input_filename, err));
+
+    eprintln!("Encoded {}x{} image in {} layers", layer.cols(), layer.rows(), layer_count);
+    match output_filename {
+        Some(output_filename) => fs::write(output_filename, image.to_sif() + "\n")
+            .unwrap_or_else(|_| panic!("Error writing {}", output_filename)),
+        None => println!("{}", image.to_sif())
     }
 }
 
-
 fn main() {
-    let rows = 6;
-    let cols = 25;
-    let layer_size = Layer::layer_size_static(rows, cols);
-    let content: String = fs::read_to_string("input.txt")
-        .expect("Error reading input");
-    let content: Vec<u8> = content.as_bytes().iter().filter(|b| *b >= &('0' as u8) && *b <= &('9' as u8)).map(|b|(b - '0' as u8) as u8).collect();
-    let len: u32 = content.len() as u32;
-    let mut cur: u32 = 0;
-    let mut layers: Vec<Layer> = Vec::<Layer>::new();
-    while cur < len {
-        layers.push(Layer::create(&content, cur, rows, cols));
-        cur = cur + layer_size;
-    }
-
-    let mut min_val = std::u32::MAX;
-    let mut min_index = std::usize::MAX;
-
-    for (index, layer) in layers.iter().enumerate() {
-        let zeros = layer.count_digit(0);
-        if zeros < min_val {
-            min_val = zeros;
-            min_index = index;
+    let args:Vec<String> = env::args().collect();
+    let encoding = args.get(1).map(String::as_str) == Some("encode");
+    let skip = if encoding || args.get(1).map(String::as_str) == Some("decode") { 2 } else { 1 };
+    let mut cols: u32 = 25;
+    let mut rows: u32 = 6;
+    let mut layer_count: u32 = 100;
+    let mut palette = Palette::default();
+    let mut input_filename: Option<String> = None;
+    let mut output_filename: Option<String> = None;
+    let mut animation = Animation {
+        gif_filename: None,
+        play: false,
+        delay: Duration::from_millis(100)
+    };
+    let mut it = args.iter().skip(skip);
+    while let Some(arg) = it.next() {
+        let mut value = || it.next().unwrap_or_else(|| panic!("Missing value for {}", arg)).as_str();
+        match arg.as_str() {
+            "--width" if !encoding => cols = value().parse::<u32>().expect("Invalid width"),
+            "--height" if !encoding => rows = value().parse::<u32>().expect("Invalid height"),
+            "--gif" if !encoding => animation.gif_filename = Some(value().to_string()),
+            "--play" if !encoding => animation.play = true,
+            "--delay" if !encoding => animation.delay = Duration::from_millis(value().parse::<u64>().expect("Invalid delay")),
+            "--layers" if encoding => layer_count = value().parse::<u32>().expect("Invalid layer count"),
+            "--palette" => palette = value().parse::<Palette>().unwrap_or_else(|err| panic!("{}", err)),
+            "--output" => output_filename = Some(value().to_string()),
+            _ if !arg.starts_with("--") => input_filename = Some(arg.clone()),
+            _ => syntax(&args[0])
         }
     }
-    let one_time_two = layers[min_index].count_digit(1)*layers[min_index].count_digit(2);
-    println!("{}", one_time_two);
 
-    let mut it = layers.iter();
-    let mut cur_layer: Layer = it.next().unwrap().clone();
-    while let Some(next_layer) = it.next() {
-        let new_layer = &cur_layer.compose(next_layer);
-        cur_layer = new_layer.clone();
+    if encoding {
+        let input_filename = input_filename.unwrap_or_else(|| syntax(&args[0]));
+        encode_file(&input_filename, layer_count, &palette, output_filename.as_deref());
+    } else {
+        decode(input_filename.as_deref().unwrap_or("input.txt"), cols, rows, &palette,
+               output_filename.as_deref().unwrap_or("output.png"), &animation);
     }
-
-    println!("{}", cur_layer);
-    cur_layer.output_image("output.png");
 }
-
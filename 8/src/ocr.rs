@@ -0,0 +1,171 @@
+use std::collections::HashSet;
+use std::fmt;
+
+// Recognition of the capital letters Advent of Code draws with a 4x6 font, one
+// blank column between letters.
+const GLYPH_WIDTH: usize = 4;
+const GLYPH_HEIGHT: usize = 6;
+const GLYPH_PITCH: usize = GLYPH_WIDTH + 1;
+
+const FONT: [(char, &str); 17] = [
+    ('A', ".##.#..##..######..##..#"),
+    ('B', "###.#..####.#..##..####."),
+    ('C', ".##.#..##...#...#..#.##."),
+    ('E', "#####...###.#...#...####"),
+    ('F', "#####...###.#...#...#..."),
+    ('G', ".##.#..##...#.###..#.###"),
+    ('H', "#..##..######..##..##..#"),
+    ('I', ".###..#...#...#...#..###"),
+    ('J', "..##...#...#...##..#.##."),
+    ('K', "#..##.#.##..#.#.#.#.#..#"),
+    ('L', "#...#...#...#...#...####"),
+    ('O', ".##.#..##..##..##..#.##."),
+    ('P', "###.#..##..####.#...#..."),
+    ('R', "###.#..##..####.#.#.#..#"),
+    ('S', ".####...#....##....####."),
+    ('U', "#..##..##..##..##..#.##."),
+    ('Z', "####...#..#..#..#...####")
+];
+
+#[derive(Debug, PartialEq)]
+pub struct UnknownGlyph {
+    pub index: usize,
+    pub col: usize,
+    pub pattern: String
+}
+
+#[derive(Debug, PartialEq)]
+pub enum OcrError {
+    Blank,
+    InvalidHeight { rows: usize },
+    UnknownGlyphs(Vec<UnknownGlyph>)
+}
+
+impl fmt::Display for OcrError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            OcrError::Blank => write!(f, "nothing to read, no pixel is lit"),
+            OcrError::InvalidHeight { rows } => write!(f, "text is {} pixels high, expected {}", rows, GLYPH_HEIGHT),
+            OcrError::UnknownGlyphs(glyphs) => {
+                for glyph in glyphs {
+                    writeln!(f, "unknown glyph #{} at column {}:", glyph.index, glyph.col)?;
+                    writeln!(f, "{}", glyph.pattern)?;
+                }
+                Ok(())
+            }
+        }
+    }
+}
+
+// grid[row][col] is true for lit pixels
+pub fn recognize_grid(grid: &[Vec<bool>]) -> Result<String, OcrError> {
+    let lit_rows: Vec<usize> = (0..grid.len()).filter(|row| grid[*row].iter().any(|lit| *lit)).collect();
+    let (first_row, last_row) = match (lit_rows.first(), lit_rows.last()) {
+        (Some(first_row), Some(last_row)) => (*first_row, *last_row),
+        _ => return Err(OcrError::Blank)
+    };
+    if last_row - first_row + 1 != GLYPH_HEIGHT {
+        return Err(OcrError::InvalidHeight { rows: last_row - first_row + 1 });
+    }
+    let lit = |row: usize, col: usize| grid[first_row + row].get(col).copied().unwrap_or(false);
+    let width = grid[first_row..=last_row].iter().map(|line| line.len()).max().unwrap();
+    let first_col = (0..width).find(|col| (0..GLYPH_HEIGHT).any(|row| lit(row, *col))).unwrap();
+    let last_col = (0..width).rev().find(|col| (0..GLYPH_HEIGHT).any(|row| lit(row, *col))).unwrap();
+
+    // Some letters (I, J) start with blank columns, so the first lit column
+    // does not tell exactly where the first letter starts.
+    let read = |origin: usize| -> Result<String, Vec<UnknownGlyph>> {
+        let mut text = String::new();
+        let mut unknown = Vec::<UnknownGlyph>::new();
+        for (index, col) in (origin..=last_col).step_by(GLYPH_PITCH).enumerate() {
+            let pattern: String = (0..GLYPH_HEIGHT * GLYPH_WIDTH)
+                .map(|i| if lit(i / GLYPH_WIDTH, col + i % GLYPH_WIDTH) { '#' } else { '.' })
+                .collect();
+            match FONT.iter().find(|(_, glyph)| *glyph == pattern) {
+                Some((letter, _)) => text.push(*letter),
+                None => unknown.push(UnknownGlyph {
+                    index,
+                    col,
+                    pattern: pattern.as_bytes().chunks(GLYPH_WIDTH)
+                        .map(|line| String::from_utf8_lossy(line).into_owned())
+                        .collect::<Vec<String>>().join("\n")
+                })
+            }
+        }
+        if unknown.is_empty() { Ok(text) } else { Err(unknown) }
+    };
+    let mut best: Option<Vec<UnknownGlyph>> = None;
+    for origin in (first_col.saturating_sub(GLYPH_WIDTH - 2)..=first_col).rev() {
+        match read(origin) {
+            Ok(text) => return Ok(text),
+            Err(unknown) => if best.as_ref().map_or(true, |best| unknown.len() < best.len()) {
+                best = Some(unknown);
+            }
+        }
+    }
+    Err(OcrError::UnknownGlyphs(best.unwrap()))
+}
+
+// Lit points as (x, y) coordinates, y growing downwards.
+pub fn recognize_points(points: &HashSet<(i32, i32)>) -> Result<String, OcrError> {
+    if points.is_empty() {
+        return Err(OcrError::Blank);
+    }
+    let min_x = points.iter().map(|p| p.0).min().unwrap();
+    let max_x = points.iter().map(|p| p.0).max().unwrap();
+    let min_y = points.iter().map(|p| p.1).min().unwrap();
+    let max_y = points.iter().map(|p| p.1).max().unwrap();
+    let mut grid = vec![vec![false; (max_x - min_x + 1) as usize]; (max_y - min_y + 1) as usize];
+    for (x, y) in points {
+        grid[(y - min_y) as usize][(x - min_x) as usize] = true;
+    }
+    recognize_grid(&grid)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn render(text: &str, margin: usize) -> Vec<Vec<bool>> {
+        let mut grid = vec![vec![false; margin]; GLYPH_HEIGHT];
+        for letter in text.chars() {
+            let glyph = FONT.iter().find(|(c, _)| *c == letter).unwrap().1.as_bytes();
+            for (row, line) in grid.iter_mut().enumerate() {
+                line.extend(glyph[row*GLYPH_WIDTH..(row + 1)*GLYPH_WIDTH].iter().map(|pixel| *pixel == b'#'));
+                line.push(false);
+            }
+        }
+        grid
+    }
+
+    #[test]
+    fn test_recognize_grid() {
+        for text in ["ABCEFGHIJKLOPRSUZ", "JZ", "IZ"].iter() {
+            for margin in 0..3 {
+                assert_eq!(recognize_grid(&render(text, margin)), Ok(text.to_string()));
+            }
+        }
+    }
+
+    #[test]
+    fn test_recognize_points() {
+        let points: HashSet<(i32, i32)> = render("HI", 0).iter().enumerate()
+            .flat_map(|(row, line)| line.iter().enumerate().filter(|(_, lit)| **lit)
+                .map(move |(col, _)| (col as i32 - 10, row as i32 + 3)))
+            .collect();
+        assert_eq!(recognize_points(&points), Ok("HI".to_string()));
+        assert_eq!(recognize_points(&HashSet::new()), Err(OcrError::Blank));
+    }
+
+    #[test]
+    fn test_unknown_glyph() {
+        let mut grid = render("ABC", 0);
+        grid[5][5] = false;
+        assert_eq!(recognize_grid(&grid), Err(OcrError::UnknownGlyphs(vec![UnknownGlyph {
+            index: 1,
+            col: 5,
+            pattern: "###.\n#..#\n###.\n#..#\n#..#\n.##.".to_string()
+        }])));
+        assert_eq!(recognize_grid(&grid[..5]), Err(OcrError::InvalidHeight { rows: 5 }));
+    }
+}
@@ -0,0 +1,87 @@
+use std::str::FromStr;
+
+use crate::SifError;
+
+// RGBA color of each digit, the digit being the index in colors.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Palette {
+    colors: Vec<[u8; 4]>
+}
+
+impl Palette {
+    // SIF pixels being single digits, there are at most 10 colors.
+    pub fn new(colors: Vec<[u8; 4]>) -> Result<Palette, SifError> {
+        if colors.len() > 10 {
+            return Err(SifError::InvalidPalette("at most 10 colors, one per digit".to_string()));
+        }
+        for (digit, color) in colors.iter().enumerate() {
+            if colors[..digit].iter().any(|other| Palette::same_color(*other, *color)) {
+                return Err(SifError::InvalidPalette(format!("color of digit {} is used twice", digit)));
+            }
+        }
+        Ok(Palette { colors })
+    }
+
+    pub fn colors(&self) -> &[[u8; 4]] {
+        &self.colors
+    }
+
+    pub fn color(&self, digit: u8) -> Option<[u8; 4]> {
+        self.colors.get(digit as usize).copied()
+    }
+
+    pub fn digit(&self, color: [u8; 4]) -> Option<u8> {
+        self.colors.iter().position(|c| Palette::same_color(*c, color)).map(|digit| digit as u8)
+    }
+
+    // all fully transparent pixels look the same, whatever their RGB values
+    fn same_color(first: [u8; 4], second: [u8; 4]) -> bool {
+        first == second || (first[3] == 0 && second[3] == 0)
+    }
+}
+
+// black, white and transparent
+impl Default for Palette {
+    fn default() -> Palette {
+        Palette {
+            colors: vec![[0, 0, 0, 255], [255, 255, 255, 255], [0, 0, 0, 0]]
+        }
+    }
+}
+
+// Comma separated RRGGBB or RRGGBBAA hexadecimal colors, for digits 0, 1, ...
+impl FromStr for Palette {
+    type Err = SifError;
+
+    fn from_str(s: &str) -> Result<Palette, SifError> {
+        let colors = s.split(',').map(|color| {
+            let color = color.trim();
+            let invalid = || SifError::InvalidPalette(format!("invalid color {:?}", color));
+            if (color.len() != 6 && color.len() != 8) || !color.is_ascii() {
+                return Err(invalid());
+            }
+            let mut rgba = [0, 0, 0, 255];
+            for (index, component) in rgba.iter_mut().enumerate().take(color.len() / 2) {
+                *component = u8::from_str_radix(&color[index*2..index*2 + 2], 16).map_err(|_| invalid())?;
+            }
+            Ok(rgba)
+        }).collect::<Result<Vec<[u8; 4]>, SifError>>()?;
+        Palette::new(colors)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_palette() {
+        assert_eq!("000000,ffffff,00000000".parse::<Palette>().unwrap(), Palette::default());
+        assert!("000000,00000".parse::<Palette>().is_err());
+        assert!("000000,000000".parse::<Palette>().is_err());
+        assert!("zz0000".parse::<Palette>().is_err());
+        let colors: Vec<String> = (0..11).map(|gray| format!("{:02x}{:02x}{:02x}", gray, gray, gray)).collect();
+        assert!(colors[..10].join(",").parse::<Palette>().is_ok());
+        assert_eq!(colors.join(",").parse::<Palette>(), Err(SifError::InvalidPalette("at most 10 colors, one per digit".to_string())));
+    }
+}
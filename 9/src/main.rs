@@ -4,13 +4,38 @@ mod intcode_computer;
 use intcode_computer::Processor;
 
 fn main() {
-    let mut computer = Processor::init("input.txt");
     let args:Vec<String> = env::args().collect();
-    if args.len() < 2 {
-        panic!("Syntax : {} <input>",args[0]);
+    let mut program_filename = "input.txt".to_string();
+    let mut detect_self_modifications = false;
+    let mut inputs = Vec::<i64>::new();
+    let mut it = args.iter().skip(1);
+    while let Some(arg) = it.next() {
+        match arg.as_str() {
+            "--detect-self-modifying" => detect_self_modifications = true,
+            "--program" => program_filename = it.next().expect("Missing program file name").clone(),
+            _ => inputs.push(arg.parse::<i64>().expect("Invalid input"))
+        }
+    }
+    if inputs.is_empty() {
+        panic!("Syntax : {} [--detect-self-modifying] [--program <file>] <input>...",args[0]);
     }
-    let input = args[1].parse::<i64>().expect("Invalid input");
 
-    let inputs = vec!(&input);
+    let mut computer = Processor::init(&program_filename);
+    if detect_self_modifications {
+        computer.detect_self_modifications();
+    }
+    let inputs: Vec<&i64> = inputs.iter().collect();
     computer.process(&inputs);
+
+    if detect_self_modifications {
+        let modifications = computer.self_modifications();
+        if modifications.is_empty() {
+            println!("No self-modifying code detected in {}", program_filename);
+        } else {
+            println!("{} self-modifying writes detected in {}:", modifications.len(), program_filename);
+            for modification in modifications {
+                println!("  {}", modification);
+            }
+        }
+    }
 }